@@ -0,0 +1,476 @@
+use std::str::FromStr;
+
+use crate::solution::{
+    self,
+    analysis::analyze,
+    generator::{generate, GeneratorConfig, GhostSpec},
+    jump::JumpTable,
+    pattern::NodePattern,
+    shortest::{shortest_instructions, SearchResult},
+    simulation::first_synchronized_step,
+    synchronization::{
+        first_common_end_step, first_partial_synchronization, synchronization_period, GhostCycle,
+    },
+    validation::{validate_structure, validate_with_alphabet},
+    walk::first_end_step,
+    Alphabet, Network, SolveError,
+};
+
+/// Runs the command given by the command line arguments (without the program
+/// name) against the input.
+///
+/// Without a command both parts are solved. Every command accepts an
+/// `--input` option to read another network than the puzzle input and an
+/// `--alphabet` option for inputs whose instructions aren't written as `LR`
+/// e. g. `--alphabet 012` for nodes with three branches. Only solving both
+/// parts and `verify` require the start and end nodes of the puzzle.
+pub fn run(input: &str, arguments: &[String]) -> Result<(), String> {
+    let mut arguments = Arguments::new(arguments);
+    let command = arguments.command();
+
+    let custom_input = arguments
+        .value("--input")?
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map_err(|error| format!("Couldn't read `{path}`: {error}"))
+        })
+        .transpose()?;
+
+    let input = custom_input.as_deref().unwrap_or(input);
+
+    let alphabet = arguments
+        .parsed_value::<Alphabet>("--alphabet")?
+        .unwrap_or_default();
+
+    // Only solving the puzzle needs the start and end nodes of both parts,
+    // every other command works on any network.
+    let validation = match command {
+        None | Some("verify") => validate_with_alphabet(input, &alphabet),
+        _ => validate_structure(input, &alphabet),
+    };
+
+    if let Err(errors) = validation {
+        let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+        return Err(errors.join("\n"));
+    }
+
+    let network = Network::parse_with_alphabet(input, alphabet);
+
+    match command {
+        None => {
+            arguments.finish()?;
+
+            let steps = solution::solve_part_one(&network)
+                .map_err(|error| format!("Can't solve part one since {error}."))?;
+            println!("{steps}");
+
+            match solution::solve_part_two(&network) {
+                Ok(steps) => println!("{steps}"),
+                Err(SolveError::Overflow(overflow)) => {
+                    let ghosts = network.nodes_where(|node| node.ends_with('A'));
+                    eprintln!(
+                        "The steps overflow u64 starting with the ghost on {}.",
+                        network.name(ghosts[overflow.index])
+                    );
+                    println!("{}", overflow.exact);
+                }
+                Err(error) => return Err(format!("Can't solve part two since {error}.")),
+            }
+        }
+        Some("export") => {
+            let dot = arguments.flag("--dot");
+            let ghost = arguments.value("--ghost")?;
+            let output = arguments.value("--output")?;
+            arguments.finish()?;
+
+            if !dot {
+                return Err("The export command requires a format e. g. --dot.".into());
+            }
+
+            write_output(output, &solution::export::to_dot(&network, ghost)?)?;
+        }
+        Some("walk") => {
+            let start = arguments.pattern("--start", "AAA")?;
+            let end = arguments.pattern("--end", "*Z")?;
+            let trace = arguments.flag("--trace");
+            let only_ends = arguments.flag("--only-ends");
+            let limit = arguments.parsed_value::<usize>("--limit")?;
+            arguments.finish()?;
+
+            let starts = matching_nodes(&network, &start)?;
+
+            let first_end = |start| {
+                let step = first_end_step(&network, start, |node| end.matches(node))
+                    .ok_or_else(|| format!("{} never reaches an end node.", network.name(start)))?;
+
+                match limit {
+                    Some(limit) if step.step >= limit as u64 => Err(format!(
+                        "No end node reached from {} within {limit} steps.",
+                        network.name(start)
+                    )),
+                    _ => Ok(step),
+                }
+            };
+
+            if trace {
+                let [start] = starts[..] else {
+                    return Err(format!(
+                        "The trace mode requires exactly one start node but {} match.",
+                        starts.len()
+                    ));
+                };
+
+                let limit = match limit {
+                    Some(limit) => limit,
+                    None => first_end(start)?.step as usize + 1,
+                };
+
+                for step in network
+                    .walk(start)
+                    .take(limit)
+                    .filter(|step| !only_ends || end.matches(step.node))
+                {
+                    println!("{step}");
+                }
+            } else {
+                for start in starts {
+                    println!("{} {}", network.name(start), first_end(start)?.step);
+                }
+            }
+        }
+        Some("analyze") => {
+            let start = arguments.pattern("--start", "*A")?;
+            let end = arguments.pattern("--end", "*Z")?;
+            arguments.finish()?;
+
+            let starts = matching_nodes(&network, &start)?;
+            println!("{}", analyze(&network, &starts, |node| end.matches(node)));
+        }
+        Some("simulate") => {
+            let start = arguments.pattern("--start", "*A")?;
+            let end = arguments.pattern("--end", "*Z")?;
+            let budget = arguments
+                .parsed_value::<u64>("--budget")?
+                .unwrap_or(1_000_000_000);
+            let threads = arguments.parsed_value::<usize>("--threads")?.unwrap_or(1);
+            let at_least = arguments.parsed_value::<usize>("--at-least")?;
+            arguments.finish()?;
+
+            let starts = matching_nodes(&network, &start)?;
+            let at_least = at_least.unwrap_or(starts.len());
+
+            if at_least > starts.len() {
+                return Err(format!(
+                    "Can't wait for {at_least} ghosts on end nodes with only {} ghosts.",
+                    starts.len()
+                ));
+            }
+
+            match first_synchronized_step(
+                &network,
+                &starts,
+                |node| end.matches(node),
+                at_least,
+                budget,
+                threads,
+            ) {
+                Some(step) => println!("{step}"),
+                None => {
+                    return Err(format!(
+                        "Fewer than {at_least} of {} ghosts are on an end node at every step up to {budget}.",
+                        starts.len()
+                    ))
+                }
+            }
+        }
+        Some("generate") => {
+            let seed = arguments.parsed_value::<u64>("--seed")?.unwrap_or(0);
+            let ghost_count = arguments.parsed_value::<usize>("--ghosts")?.unwrap_or(2);
+            let instruction_count = arguments
+                .parsed_value::<usize>("--instructions")?
+                .unwrap_or(5);
+            let tail_lengths = arguments.list("--tail", ghost_count, 2)?;
+            let cycle_lengths = arguments.list("--cycle", ghost_count, 10)?;
+            let end_offsets = arguments.list("--offset", ghost_count, 8)?;
+            let noise_nodes = arguments.parsed_value::<usize>("--noise")?.unwrap_or(10);
+            let output = arguments.value("--output")?;
+            arguments.finish()?;
+
+            let ghosts = (0..ghost_count)
+                .map(|index| GhostSpec {
+                    tail_length: tail_lengths[index],
+                    cycle_length: cycle_lengths[index],
+                    end_offset: end_offsets[index],
+                })
+                .collect();
+
+            let generated = generate(&GeneratorConfig {
+                seed,
+                instruction_count,
+                ghosts,
+                noise_nodes,
+            })?;
+
+            let answers = format!(
+                "part one: {}\npart two: {}\n",
+                generated.part_one,
+                generated
+                    .part_two
+                    .map_or("none".to_string(), |steps| steps.to_string())
+            );
+
+            write_output(output, &generated.input)?;
+
+            match output {
+                Some(path) => write_output(Some(&format!("{path}.answers")), &answers)?,
+                None => eprint!("{answers}"),
+            }
+        }
+        Some("sync") => {
+            let start = arguments.pattern("--start", "*A")?;
+            let end = arguments.pattern("--end", "*Z")?;
+            let subset = arguments.pattern("--subset", "*")?;
+            let at_least = arguments.parsed_value::<usize>("--at-least")?;
+            arguments.finish()?;
+
+            let ghosts = matching_nodes(&network, &start)?
+                .into_iter()
+                .map(|start| GhostCycle::detect(&network, start, |node| end.matches(node)))
+                .collect::<Vec<_>>();
+
+            for ghost in &ghosts {
+                println!(
+                    "{} tail {} cycle {} end steps {:?} + {:?} every cycle",
+                    network.name(ghost.start),
+                    ghost.tail_length,
+                    ghost.cycle_length,
+                    ghost.tail_ends,
+                    ghost.cycle_ends
+                );
+            }
+
+            let selected = ghosts
+                .iter()
+                .filter(|ghost| subset.matches(network.name(ghost.start)))
+                .collect::<Vec<_>>();
+
+            let overflow = |overflow| format!("Can't synchronize the ghosts since the {overflow}.");
+            let first = first_common_end_step(&selected).map_err(overflow)?;
+            let period = synchronization_period(&selected).map_err(overflow)?;
+
+            match (first, period) {
+                (Some(first), Some(period)) => println!(
+                    "{} ghosts synchronize first at {first} and then every {period} steps",
+                    selected.len()
+                ),
+                (Some(first), None) => {
+                    println!("{} ghosts synchronize only at {first}", selected.len())
+                }
+                (None, _) => println!(
+                    "{} ghosts never all stand on end nodes together",
+                    selected.len()
+                ),
+            }
+
+            if let Some(at_least) = at_least {
+                match first_partial_synchronization(&ghosts, at_least).map_err(overflow)? {
+                    Some((step, members)) => {
+                        let members = members
+                            .iter()
+                            .map(|&index| network.name(ghosts[index].start))
+                            .collect::<Vec<_>>();
+
+                        println!(
+                            "{at_least} ghosts synchronize first at {step} ({})",
+                            members.join(" ")
+                        );
+                    }
+                    None => println!("{at_least} ghosts never all stand on end nodes together"),
+                }
+            }
+        }
+        Some("shortest") => {
+            let start = arguments.pattern("--start", "AAA")?;
+            let end = arguments.pattern("--end", "ZZZ")?;
+            let limit = arguments
+                .parsed_value::<usize>("--limit")?
+                .unwrap_or(1_000_000);
+            arguments.finish()?;
+
+            let starts = matching_nodes(&network, &start)?;
+
+            match shortest_instructions(&network, &starts, |node| end.matches(node), limit) {
+                SearchResult::Found(instructions) => {
+                    let instructions = instructions
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<String>();
+
+                    println!("{} {instructions}", instructions.chars().count());
+                }
+                SearchResult::Unreachable => {
+                    return Err(format!("No instructions lead from `{start}` to `{end}`."))
+                }
+                SearchResult::LimitExceeded => {
+                    return Err(format!("Gave up after visiting {limit} states."))
+                }
+            }
+        }
+        Some("position") => {
+            let start = arguments.pattern("--start", "*A")?;
+            let steps = arguments
+                .parsed_value::<u64>("--steps")?
+                .ok_or("The position command requires --steps.")?;
+            arguments.finish()?;
+
+            let table = JumpTable::new(&network);
+            let starts = matching_nodes(&network, &start)?;
+
+            for start in starts {
+                let position = table.position_after(start, steps);
+                println!("{} {}", network.name(start), network.name(position));
+            }
+        }
+        Some("verify") => {
+            arguments.finish()?;
+
+            let steps = solution::solve_part_two(&network)
+                .map_err(|error| format!("Can't verify since {error}."))?;
+            let table = JumpTable::new(&network);
+
+            let stray_ghosts = network
+                .nodes_where(|node| node.ends_with('A'))
+                .into_iter()
+                .map(|start| (start, table.position_after(start, steps)))
+                .filter(|&(_, position)| !network.name(position).ends_with('Z'))
+                .map(|(start, position)| {
+                    format!("{} is on {}", network.name(start), network.name(position))
+                })
+                .collect::<Vec<_>>();
+
+            if !stray_ghosts.is_empty() {
+                return Err(format!(
+                    "Not every ghost is on an end node after {steps} steps: {}.",
+                    stray_ghosts.join(", ")
+                ));
+            }
+
+            println!("Every ghost is on an end node after {steps} steps.");
+        }
+        Some(command) => return Err(format!("Unknown command `{command}`.")),
+    }
+
+    Ok(())
+}
+
+fn matching_nodes(network: &Network, pattern: &NodePattern) -> Result<Vec<usize>, String> {
+    let nodes = network.nodes_where(|node| pattern.matches(node));
+
+    if nodes.is_empty() {
+        Err(format!("No node matches `{pattern}`."))
+    } else {
+        Ok(nodes)
+    }
+}
+
+fn write_output(path: Option<&str>, content: &str) -> Result<(), String> {
+    match path {
+        Some(path) => std::fs::write(path, content)
+            .map_err(|error| format!("Couldn't write to `{path}`: {error}")),
+        None => {
+            print!("{content}");
+            Ok(())
+        }
+    }
+}
+
+/// Minimal parser for a command followed by `--flag` and `--option value`
+/// arguments in any order.
+struct Arguments<'a> {
+    remaining: Vec<&'a str>,
+}
+
+impl<'a> Arguments<'a> {
+    fn new(arguments: &'a [String]) -> Self {
+        Self {
+            remaining: arguments.iter().map(String::as_str).collect(),
+        }
+    }
+
+    fn command(&mut self) -> Option<&'a str> {
+        match self.remaining.first() {
+            Some(first) if !first.starts_with("--") => Some(self.remaining.remove(0)),
+            _ => None,
+        }
+    }
+
+    fn flag(&mut self, name: &str) -> bool {
+        match self.remaining.iter().position(|argument| *argument == name) {
+            Some(index) => {
+                self.remaining.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn value(&mut self, name: &str) -> Result<Option<&'a str>, String> {
+        let Some(index) = self.remaining.iter().position(|argument| *argument == name) else {
+            return Ok(None);
+        };
+
+        if index + 1 >= self.remaining.len() {
+            return Err(format!("Option `{name}` requires a value."));
+        }
+
+        self.remaining.remove(index);
+        Ok(Some(self.remaining.remove(index)))
+    }
+
+    fn parsed_value<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        self.value(name)?
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid value `{value}` for option `{name}`."))
+            })
+            .transpose()
+    }
+
+    fn pattern(&mut self, name: &str, default: &str) -> Result<NodePattern, String> {
+        self.value(name)?.unwrap_or(default).parse()
+    }
+
+    /// Comma separated values with one value per item, a single value is used
+    /// for every item.
+    fn list(&mut self, name: &str, items: usize, default: usize) -> Result<Vec<usize>, String> {
+        let Some(raw_values) = self.value(name)? else {
+            return Ok(vec![default; items]);
+        };
+
+        let values = raw_values
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid value `{value}` for option `{name}`."))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match values[..] {
+            [value] => Ok(vec![value; items]),
+            _ if values.len() == items => Ok(values),
+            _ => Err(format!(
+                "Option `{name}` needs one value or one value per item ({items})."
+            )),
+        }
+    }
+
+    fn finish(self) -> Result<(), String> {
+        match self.remaining.first() {
+            Some(unexpected) => Err(format!("Unexpected argument `{unexpected}`.")),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,51 +1,254 @@
 fn main() {
     let input = include_str!("../../input/Day08.txt");
-    println!("{}", solution::part_one(input));
-    println!("{}", solution::part_two(input));
+    let arguments = std::env::args().skip(1).collect::<Vec<_>>();
+
+    if arguments.is_empty() {
+        if let Err(errors) = solution::validation::validate(input) {
+            for error in errors {
+                eprintln!("{error}");
+            }
+
+            std::process::exit(1);
+        }
+
+        println!("{}", solution::part_one(input));
+        println!("{}", solution::part_two(input));
+        return;
+    }
+
+    if let Err(error) = cli::run(input, &arguments) {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
 }
 
+mod cli;
+
 mod solution {
-    use std::{collections::HashMap, ops::Index};
+    use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+    pub mod analysis;
+    pub mod export;
+    pub mod generator;
+    pub mod jump;
+    pub mod lcm;
+    pub mod pattern;
+    pub mod shortest;
+    pub mod simulation;
+    pub mod synchronization;
+    pub mod validation;
+    pub mod walk;
 
-    #[derive(Debug)]
-    enum Instruction {
-        Left,
-        Right,
+    use lcm::{find_least_common_multiple, LcmOverflow};
+    use walk::{first_end_step, Walk};
+
+    /// Picks the branch with the index of the symbol in the alphabet.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Instruction {
+        pub branch: usize,
+        pub symbol: char,
     }
 
-    fn parse_input(input: &str) -> (Vec<Instruction>, HashMap<&str, (&str, &str)>) {
-        let (raw_instructions, raw_map_points) = input
-            .split_once("\n\n")
-            .expect("Expect instructions then an empty line and then the map points.");
+    impl Display for Instruction {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.symbol)
+        }
+    }
 
-        let instructions = raw_instructions
-            .chars()
-            .map(|raw_instruction| match raw_instruction {
-                'L' => Instruction::Left,
-                'R' => Instruction::Right,
-                _ => panic!("Instructions have to be either L or R."),
-            })
-            .collect();
-
-        let map_points = raw_map_points
-            .split('\n')
-            .filter(|line| !line.is_empty())
-            .map(|raw_map_point| {
-                let (name, connected_points) = raw_map_point
-                    .split_once(" = (")
-                    .expect("Expect map point in format 'name = (left, right)'.");
-
-                let (left, right) = connected_points
-                    .strip_suffix(')')
-                    .expect("Expect closing brace at the end of a map point.")
-                    .split_once(", ")
-                    .expect("Expect connected points to be comma space separated.");
-
-                (name, (left, right))
-            })
-            .collect();
+    /// The symbols instructions are written with, the first symbol picks the
+    /// first branch of a node, the second the second one and so on.
+    ///
+    /// Defaults to `LR` like in the AoC puzzle.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Alphabet {
+        symbols: Vec<char>,
+    }
+
+    impl Default for Alphabet {
+        fn default() -> Self {
+            Self {
+                symbols: vec!['L', 'R'],
+            }
+        }
+    }
+
+    impl FromStr for Alphabet {
+        type Err = String;
+
+        fn from_str(raw_alphabet: &str) -> Result<Self, Self::Err> {
+            let symbols = raw_alphabet.chars().collect::<Vec<_>>();
+
+            if symbols.is_empty() {
+                return Err("An alphabet needs at least one symbol.".into());
+            }
+
+            for (index, symbol) in symbols.iter().enumerate() {
+                if symbol.is_whitespace() || symbols[..index].contains(symbol) {
+                    return Err(format!("Invalid or duplicate alphabet symbol `{symbol}`."));
+                }
+            }
+
+            Ok(Self { symbols })
+        }
+    }
+
+    impl Alphabet {
+        pub fn instruction(&self, symbol: char) -> Option<Instruction> {
+            self.symbols
+                .iter()
+                .position(|candidate| *candidate == symbol)
+                .map(|branch| Instruction { branch, symbol })
+        }
+
+        /// Every instruction of the alphabet in branch order.
+        pub fn instructions(&self) -> impl Iterator<Item = Instruction> + '_ {
+            self.symbols
+                .iter()
+                .enumerate()
+                .map(|(branch, &symbol)| Instruction { branch, symbol })
+        }
+    }
+
+    /// The instructions together with the node network they are followed in.
+    ///
+    /// Nodes are interned, they are referred to by their index in definition
+    /// order and only resolved to their names when needed.
+    pub struct Network<'a> {
+        alphabet: Alphabet,
+        instructions: Vec<Instruction>,
+        names: Vec<&'a str>,
+        indices: HashMap<&'a str, usize>,
+        connections: Vec<Vec<usize>>,
+    }
 
-        (instructions, map_points)
+    impl<'a> Network<'a> {
+        /// Parses the network with the default `LR` alphabet.
+        pub fn parse(input: &'a str) -> Self {
+            Self::parse_with_alphabet(input, Alphabet::default())
+        }
+
+        /// Parses the network, the input should have been validated first
+        /// because undefined nodes and unknown instructions cause a panic.
+        pub fn parse_with_alphabet(input: &'a str, alphabet: Alphabet) -> Self {
+            // Splits the lines like the validation, which also accepts `\r\n`.
+            let mut lines = input.lines();
+            let raw_instructions = lines.next().unwrap_or_default();
+
+            assert!(
+                lines.next().is_some_and(str::is_empty),
+                "Expect instructions then an empty line and then the map points."
+            );
+
+            let instructions = raw_instructions
+                .chars()
+                .map(|raw_instruction| {
+                    alphabet
+                        .instruction(raw_instruction)
+                        .expect("Instructions have to be part of the alphabet.")
+                })
+                .collect();
+
+            let map_points = lines
+                .filter(|line| !line.is_empty())
+                .map(parse_map_point)
+                .collect::<Vec<_>>();
+
+            let mut names = vec![];
+            let mut indices = HashMap::new();
+
+            for (name, _) in &map_points {
+                indices.entry(*name).or_insert_with(|| {
+                    names.push(*name);
+                    names.len() - 1
+                });
+            }
+
+            let index_of = |name| {
+                *indices
+                    .get(name)
+                    .unwrap_or_else(|| panic!("Node `{name}` is referenced but never defined."))
+            };
+
+            let mut connections = vec![vec![]; names.len()];
+
+            for (name, connected_points) in map_points {
+                connections[index_of(name)] = connected_points.into_iter().map(index_of).collect();
+            }
+
+            Self {
+                alphabet,
+                instructions,
+                names,
+                indices,
+                connections,
+            }
+        }
+
+        pub fn index_of(&self, name: &str) -> Option<usize> {
+            self.indices.get(name).copied()
+        }
+
+        pub fn name(&self, index: usize) -> &'a str {
+            self.names[index]
+        }
+
+        /// Indices of all nodes whose name satisfies the predicate.
+        pub fn nodes_where(&self, predicate: impl Fn(&str) -> bool) -> Vec<usize> {
+            (0..self.names.len())
+                .filter(|&index| predicate(self.names[index]))
+                .collect()
+        }
+
+        /// The node reached by following the instruction from the node.
+        fn next_node(&self, node: usize, instruction: Instruction) -> usize {
+            *self.connections[node]
+                .get(instruction.branch)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Node `{}` has no branch for instruction `{instruction}`.",
+                        self.names[node]
+                    )
+                })
+        }
+
+        /// Starts an endless walk at the node following the instructions.
+        pub fn walk(&self, start: usize) -> Walk<'_, 'a> {
+            Walk::new(self, start)
+        }
+    }
+
+    fn parse_map_point(raw_map_point: &str) -> (&str, Vec<&str>) {
+        try_parse_map_point(raw_map_point)
+            .expect("Expect map point in format 'name = (left, right)'.")
+    }
+
+    /// The name and the connected points of the map point, `None` if it isn't
+    /// in the format `name = (left, right)`.
+    fn try_parse_map_point(raw_map_point: &str) -> Option<(&str, Vec<&str>)> {
+        let (name, connected_points) = raw_map_point.split_once(" = (")?;
+        let connected_points = connected_points.strip_suffix(')')?.split(", ").collect();
+
+        Some((name, connected_points))
+    }
+
+    /// Why a part has no answer.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum SolveError<'a> {
+        /// The walk from the start repeats itself without reaching an end.
+        Unreachable { start: &'a str },
+        /// The steps of part two don't fit into a `u64`.
+        Overflow(LcmOverflow),
+    }
+
+    impl Display for SolveError<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Unreachable { start } => {
+                    write!(f, "the walk from `{start}` never reaches an end node")
+                }
+                Self::Overflow(overflow) => write!(f, "the {overflow}"),
+            }
+        }
     }
 
     /// Implementation of the solution for the following problem
@@ -104,8 +307,14 @@ mod solution {
     /// Starting at `AAA`, follow the left/right instructions. **How many steps
     /// are required to reach `ZZZ`?**
     pub fn part_one(input: &str) -> u64 {
-        let (instructions, points) = parse_input(input);
-        count_steps_until(&instructions, &points, "AAA", |point| point == "ZZZ")
+        solve_part_one(&Network::parse(input)).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Part one on an already parsed network e. g. one with a custom alphabet.
+    pub fn solve_part_one<'a>(network: &Network<'a>) -> Result<u64, SolveError<'a>> {
+        let start = network.index_of("AAA").expect("Part one starts at AAA.");
+
+        count_steps_until(network, start, |point| point == "ZZZ")
     }
 
     /// Implementation of the solution for the following problem (day 08 part two)
@@ -153,17 +362,17 @@ mod solution {
     ///
     /// - Step 0: You are at `11A` and `22A`.
     /// - Step 1: You choose all of the **left** paths, leading you to `11B`
-    /// and `22B`.
+    ///   and `22B`.
     /// - Step 2: You choose all of the **right** paths, leading you to
-    /// **`11Z`** and `22C`.
+    ///   **`11Z`** and `22C`.
     /// - Step 3: You choose all of the **left** paths, leading you to `11B` and
-    /// **`22Z`**.
+    ///   **`22Z`**.
     /// - Step 4: You choose all of the **right** paths, leading you to
-    /// **`11Z`** and `22B`.
+    ///   **`11Z`** and `22B`.
     /// - Step 5: You choose all of the **left** paths, leading you to `11B` and
-    /// `22C`.
+    ///   `22C`.
     /// - Step 6: You choose all of the **right** paths, leading you to
-    /// **`11Z`** and **`22Z`**.
+    ///   **`11Z`** and **`22Z`**.
     ///
     /// So, in this example, you end up entirely on nodes that end in `Z` after
     /// **`6`** steps.
@@ -171,97 +380,280 @@ mod solution {
     /// Simultaneously start on every node that ends with `A`. **How many steps
     /// does it take before you're only on nodes that end with `Z`?**
     pub fn part_two(input: &str) -> u64 {
-        let (instructions, points) = parse_input(input);
+        solve_part_two(&Network::parse(input)).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Part two on an already parsed network e. g. one with a custom alphabet.
+    ///
+    /// The index of an overflow refers to the ghosts in definition order of
+    /// their start nodes.
+    pub fn solve_part_two<'a>(network: &Network<'a>) -> Result<u64, SolveError<'a>> {
+        let minimum_steps = network
+            .nodes_where(|point| point.ends_with('A'))
+            .into_iter()
+            .map(|start| count_steps_until(network, start, |point| point.ends_with('Z')))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        find_least_common_multiple(&minimum_steps).map_err(SolveError::Overflow)
+    }
 
-        let minimum_steps = points
-            .keys()
-            .filter(|point| point.ends_with('A'))
-            .map(|point| {
-                count_steps_until(&instructions, &points, point, |point| point.ends_with('Z'))
+    fn count_steps_until<'a>(
+        network: &Network<'a>,
+        start: usize,
+        should_stop: impl Fn(&str) -> bool,
+    ) -> Result<u64, SolveError<'a>> {
+        first_end_step(network, start, should_stop)
+            .map(|step| step.step)
+            .ok_or(SolveError::Unreachable {
+                start: network.name(start),
             })
-            .collect::<Vec<_>>();
+    }
+}
 
-        find_least_common_multiple(&minimum_steps)
-    }
-
-    fn count_steps_until<'p, 'm, M>(
-        instructions: &[Instruction],
-        points: &M,
-        point: &'p str,
-        should_stop: fn(&str) -> bool,
-    ) -> u64
-    where
-        M: Index<&'p str, Output = (&'m str, &'m str)>,
-        'm: 'p,
-    {
-        let mut next_instruction = 0usize;
-        let mut steps = 0;
-        let mut current_point = point;
-
-        loop {
-            if should_stop(current_point) {
-                break;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            match instructions[next_instruction] {
-                Instruction::Left => current_point = &points[current_point].0,
-                Instruction::Right => current_point = &points[current_point].1,
-            }
+    #[test]
+    fn part_one() {
+        const INPUT: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
 
-            next_instruction += 1;
+        assert_eq!(solution::part_one(INPUT), 6)
+    }
 
-            if next_instruction >= instructions.len() {
-                next_instruction = 0;
-            }
+    #[test]
+    fn part_two() {
+        const INPUT: &str = "LR
 
-            steps += 1;
-        }
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
 
-        steps
+        assert_eq!(solution::part_two(INPUT), 6)
     }
 
-    fn greatest_common_divisor(mut left: u64, mut right: u64) -> u64 {
-        while right != 0 {
-            if left > right {
-                std::mem::swap(&mut left, &mut right);
-            }
-            right %= left;
-        }
+    #[test]
+    fn unreachable_end() {
+        const INPUT: &str = "L
+
+AAA = (BBB, BBB)
+BBB = (AAA, AAA)
+ZZZ = (ZZZ, ZZZ)";
 
-        left
+        let network = solution::Network::parse(INPUT);
+        let unreachable = Err(solution::SolveError::Unreachable { start: "AAA" });
+
+        assert_eq!(solution::validation::validate(INPUT), Ok(()));
+        assert_eq!(solution::solve_part_one(&network), unreachable);
+        assert_eq!(solution::solve_part_two(&network), unreachable);
     }
 
-    fn find_least_common_multiple(numbers: &[u64]) -> u64 {
-        match numbers.len() {
-            0 => panic!("Can't get least common multiple of empty slice."),
-            1 => numbers[0],
-            _ => {
-                let left = numbers[0];
-                let right = find_least_common_multiple(&numbers[1..]);
+    #[test]
+    fn validate() {
+        const VALID_INPUT: &str = "LLR
 
-                left * right / greatest_common_divisor(left, right)
-            }
-        }
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+        assert_eq!(solution::validation::validate(VALID_INPUT), Ok(()));
+
+        let crlf_input = VALID_INPUT.replace('\n', "\r\n");
+        assert_eq!(solution::validation::validate(&crlf_input), Ok(()));
+        assert_eq!(solution::part_one(&crlf_input), 6);
+
+        const INVALID_INPUT: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11B = (11Z, 11Z)
+22A = (22B, XXX)
+11Z = (11B, YYY)";
+
+        assert_eq!(
+            solution::validation::validate(INVALID_INPUT),
+            Err(vec![
+                solution::validation::ValidationError::DuplicateNode {
+                    name: "11B",
+                    line: 5,
+                    first_line: 4
+                },
+                solution::validation::ValidationError::UndefinedNode {
+                    name: "XXX",
+                    line: 3
+                },
+                solution::validation::ValidationError::UndefinedNode {
+                    name: "XXX",
+                    line: 4
+                },
+                solution::validation::ValidationError::UndefinedNode {
+                    name: "22B",
+                    line: 6
+                },
+                solution::validation::ValidationError::UndefinedNode {
+                    name: "XXX",
+                    line: 6
+                },
+                solution::validation::ValidationError::UndefinedNode {
+                    name: "YYY",
+                    line: 7
+                },
+                solution::validation::ValidationError::MissingNode { name: "AAA" },
+                solution::validation::ValidationError::MissingNode { name: "ZZZ" },
+                solution::validation::ValidationError::StartEndMismatch {
+                    starts: vec![("11A", 3), ("22A", 6)],
+                    ends: vec![("11Z", 7)]
+                },
+            ])
+        );
+
+        const MALFORMED_INPUT: &str = "
+AAA = (ZZZ, ZZZ)
+
+ZZZ = ZZZ, ZZZ
+ZZZ = (ZZZ, ZZZ";
+
+        assert_eq!(
+            solution::validation::validate(MALFORMED_INPUT),
+            Err(vec![
+                solution::validation::ValidationError::EmptyInstructions,
+                solution::validation::ValidationError::MalformedLine { line: 2 },
+                solution::validation::ValidationError::MalformedLine { line: 4 },
+                solution::validation::ValidationError::MalformedLine { line: 5 },
+                solution::validation::ValidationError::MissingNode { name: "AAA" },
+                solution::validation::ValidationError::MissingNode { name: "ZZZ" },
+            ])
+        );
+
+        const PART_TWO_INPUT: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+XXX = (XXX, XXX)";
+
+        let alphabet = solution::Alphabet::default();
+        assert!(solution::validation::validate(PART_TWO_INPUT).is_err());
+        assert_eq!(
+            solution::validation::validate_structure(PART_TWO_INPUT, &alphabet),
+            Ok(())
+        );
+        assert_eq!(
+            solution::validation::validate_structure(INVALID_INPUT, &alphabet)
+                .map_err(|errors| errors.len()),
+            Err(6)
+        );
     }
+
+    #[test]
+    fn export_dot() {
+        const INPUT: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+        assert_eq!(
+            solution::export::to_dot(&solution::Network::parse(INPUT), Some("BBB")).unwrap(),
+            r#"digraph network {
+    "AAA" [style=filled, fillcolor=palegreen];
+    "BBB";
+    "ZZZ" [style=filled, fillcolor=lightcoral];
+    "AAA" -> "BBB" [label="L", color=blue, penwidth=3];
+    "AAA" -> "BBB" [label="R"];
+    "BBB" -> "AAA" [label="L", color=blue, penwidth=3];
+    "BBB" -> "ZZZ" [label="R", color=blue, penwidth=3];
+    "ZZZ" -> "ZZZ" [label="L", color=blue, penwidth=3];
+    "ZZZ" -> "ZZZ" [label="R", color=blue, penwidth=3];
 }
+"#
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert!(solution::export::to_dot(&solution::Network::parse(INPUT), Some("CCC")).is_err());
+
+        let alphabet = "\"\\".parse::<solution::Alphabet>().unwrap();
+        let network = solution::Network::parse_with_alphabet(
+            "\"\n\na\"b = (c\\d, a\"b)\nc\\d = (a\"b, c\\d)",
+            alphabet,
+        );
+        assert_eq!(
+            solution::export::to_dot(&network, None).unwrap(),
+            r#"digraph network {
+    "a\"b";
+    "c\\d";
+    "a\"b" -> "c\\d" [label="\""];
+    "a\"b" -> "a\"b" [label="\\"];
+    "c\\d" -> "a\"b" [label="\""];
+    "c\\d" -> "c\\d" [label="\\"];
+}
+"#
+        );
+    }
 
     #[test]
-    fn part_one() {
+    fn walk() {
         const INPUT: &str = "LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
 
-        assert_eq!(solution::part_one(INPUT), 6)
+        let network = solution::Network::parse(INPUT);
+        let start = network.index_of("AAA").unwrap();
+
+        assert_eq!(
+            network
+                .walk(start)
+                .take(7)
+                .map(|step| step.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "0 0 L AAA",
+                "1 1 L BBB",
+                "2 2 R AAA",
+                "3 0 L BBB",
+                "4 1 L AAA",
+                "5 2 R BBB",
+                "6 0 L ZZZ"
+            ]
+        );
+
+        let end_hits = network
+            .walk(start)
+            .filter(|step| step.node == "ZZZ")
+            .take(2)
+            .map(|step| step.step)
+            .collect::<Vec<_>>();
+
+        assert_eq!(end_hits, [6, 7]);
+
+        let mut until_repeat = network.walk(start).until_repeat();
+        assert_eq!(until_repeat.repetition(), None);
+        assert_eq!(until_repeat.by_ref().count(), 9);
+        assert_eq!(until_repeat.repetition(), Some((6, 9)));
+
+        let is_end = |node: &str| node == "ZZZ";
+        assert_eq!(
+            solution::walk::first_end_step(&network, start, is_end).map(|step| step.step),
+            Some(6)
+        );
+        assert_eq!(
+            solution::walk::first_end_step(&network, start, |node| node == "NOPE"),
+            None
+        );
     }
 
     #[test]
-    fn part_two() {
+    fn jump_table() {
         const INPUT: &str = "LR
 
 11A = (11B, XXX)
@@ -273,6 +665,483 @@ ZZZ = (ZZZ, ZZZ)";
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
 
-        assert_eq!(solution::part_two(INPUT), 6)
+        let network = solution::Network::parse(INPUT);
+        let table = solution::jump::JumpTable::new(&network);
+
+        for start in network.nodes_where(|node| node.ends_with('A')) {
+            for (step, walk_step) in network.walk(start).take(50).enumerate() {
+                assert_eq!(
+                    network.name(table.position_after(start, step as u64)),
+                    walk_step.node
+                );
+            }
+        }
+
+        let ghost = network.index_of("22A").unwrap();
+        assert_eq!(
+            network.name(table.position_after(ghost, 3_000_000_000)),
+            "22Z"
+        );
+    }
+
+    #[test]
+    fn node_pattern() {
+        let pattern = "*A, B?B,CCC"
+            .parse::<solution::pattern::NodePattern>()
+            .unwrap();
+
+        for matching in ["AAA", "11A", "A", "BXB", "CCC"] {
+            assert!(pattern.matches(matching), "{matching}");
+        }
+
+        for not_matching in ["AAZ", "BB", "BXXB", "CC"] {
+            assert!(!pattern.matches(not_matching), "{not_matching}");
+        }
+
+        assert!("AAA,".parse::<solution::pattern::NodePattern>().is_err());
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        const INPUT: &str = "021
+
+AAA = (BBB, CCC, AAA)
+BBB = (AAA, AAA, ZZZ)
+CCC = (AAA, AAA, BBB)
+ZZZ = (ZZZ, ZZZ, ZZZ)";
+
+        let alphabet = "012".parse::<solution::Alphabet>().unwrap();
+
+        assert_eq!(
+            solution::validation::validate_with_alphabet(INPUT, &alphabet),
+            Ok(())
+        );
+
+        let network = solution::Network::parse_with_alphabet(INPUT, alphabet);
+        assert_eq!(solution::solve_part_one(&network), Ok(2));
+
+        let alphabet = "01".parse::<solution::Alphabet>().unwrap();
+
+        assert_eq!(
+            solution::validation::validate_with_alphabet(INPUT, &alphabet),
+            Err(vec![
+                solution::validation::ValidationError::UnknownInstruction {
+                    symbol: '2',
+                    column: 2
+                }
+            ])
+        );
+
+        let alphabet = "0123".parse::<solution::Alphabet>().unwrap();
+
+        assert_eq!(
+            solution::validation::validate_with_alphabet(
+                "3\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ, ZZZ, ZZZ)",
+                &alphabet
+            ),
+            Err(vec![solution::validation::ValidationError::MissingBranch {
+                name: "AAA",
+                line: 3,
+                branches: 2,
+                required: 4
+            }])
+        );
+
+        assert!("LRL".parse::<solution::Alphabet>().is_err());
+    }
+
+    #[test]
+    fn shortest_instructions() {
+        use solution::shortest::{shortest_instructions, SearchResult};
+
+        const INPUT: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+        let network = solution::Network::parse(INPUT);
+        let to_string = |result| match result {
+            SearchResult::Found(instructions) => instructions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<String>(),
+            other => panic!("Expected instructions but got {other:?}"),
+        };
+
+        let start = network.index_of("11A").unwrap();
+        let target = |node: &str| node == "11Z";
+        assert_eq!(
+            to_string(shortest_instructions(&network, &[start], target, 100)),
+            "LR"
+        );
+
+        let starts = network.nodes_where(|node| node.ends_with('A'));
+        let is_end = |node: &str| node.ends_with('Z');
+        assert_eq!(
+            to_string(shortest_instructions(&network, &starts, is_end, 100)),
+            "LRLRLR"
+        );
+
+        let dead_end = network.index_of("XXX").unwrap();
+        assert_eq!(
+            shortest_instructions(&network, &[dead_end], is_end, 100),
+            SearchResult::Unreachable
+        );
+        assert_eq!(
+            shortest_instructions(&network, &starts, is_end, 2),
+            SearchResult::LimitExceeded
+        );
+    }
+
+    #[test]
+    fn analyze() {
+        const INPUT: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+33A = (XXX, 33Z)
+33Z = (33Z, 33Z)
+DDD = (11A, 22A)";
+
+        let network = solution::Network::parse(INPUT);
+        let starts = network.nodes_where(|node| node.ends_with('A'));
+        let analysis = solution::analysis::analyze(&network, &starts, |node| node.ends_with('Z'));
+
+        let mut components = analysis.components.clone();
+        components.sort();
+
+        assert_eq!(
+            components,
+            [
+                vec!["11A"],
+                vec!["11B", "11Z"],
+                vec!["22A"],
+                vec!["22B", "22C", "22Z"],
+                vec!["33A"],
+                vec!["33Z"],
+                vec!["DDD"],
+                vec!["XXX"],
+            ]
+        );
+
+        let ends_hit = analysis
+            .starts
+            .iter()
+            .map(|start| (start.start, start.reachable.len(), start.ends_hit.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ends_hit,
+            [
+                ("11A", 4, vec!["11Z"]),
+                ("22A", 5, vec!["22Z"]),
+                ("33A", 3, vec![])
+            ]
+        );
+
+        assert_eq!(analysis.dead_nodes, ["DDD"]);
+    }
+
+    #[test]
+    fn least_common_multiple() {
+        use solution::lcm::{find_least_common_multiple, greatest_common_divisor};
+
+        assert_eq!(greatest_common_divisor(0, 5), 5);
+        assert_eq!(greatest_common_divisor(5, 0), 5);
+        assert_eq!(greatest_common_divisor(0, 0), 0);
+        assert_eq!(greatest_common_divisor(12, 18), 6);
+
+        assert_eq!(find_least_common_multiple(&[2, 3, 4]), Ok(12));
+        assert_eq!(find_least_common_multiple(&[0, 3, 4]), Ok(0));
+
+        let overflow = find_least_common_multiple(&[u64::MAX, 2, 4, u64::MAX]).unwrap_err();
+        assert_eq!(overflow.index, 1);
+        assert_eq!(overflow.exact.to_string(), "73786976294838206460");
+    }
+
+    #[test]
+    fn lockstep_simulation() {
+        use solution::simulation::first_synchronized_step;
+
+        const INPUT: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+33A = (33B, 33B)
+33B = (33C, 33C)
+33C = (33D, 33D)
+33D = (33E, 33E)
+33E = (33Z, 33Z)
+33Z = (33B, 33B)
+XXX = (XXX, XXX)";
+
+        let network = solution::Network::parse(INPUT);
+        let starts = network.nodes_where(|node| node.ends_with('A'));
+        let is_end = |node: &str| node.ends_with('Z');
+
+        let expected = solution::solve_part_two(&network).unwrap();
+        assert_eq!(expected, 30);
+
+        for threads in [1, 2, 4] {
+            assert_eq!(
+                first_synchronized_step(&network, &starts, is_end, 3, 10_000, threads),
+                Some(expected)
+            );
+        }
+
+        assert_eq!(
+            first_synchronized_step(&network, &starts, is_end, 2, 10_000, 1),
+            Some(6)
+        );
+        assert_eq!(
+            first_synchronized_step(&network, &starts, is_end, 3, 29, 2),
+            None
+        );
+        assert_eq!(
+            first_synchronized_step(&network, &starts, is_end, 4, 10_000, 2),
+            None
+        );
+
+        // The blocks end right before and right after the step.
+        for threads in [1, 3] {
+            assert_eq!(
+                first_synchronized_step(&network, &starts, is_end, 3, 30, threads),
+                Some(30)
+            );
+        }
+    }
+
+    #[test]
+    fn generated_networks() {
+        use solution::{
+            generator::{generate, GeneratorConfig, GhostSpec},
+            simulation::first_synchronized_step,
+        };
+
+        let ghost = |tail_length, cycle_length, end_offset| GhostSpec {
+            tail_length,
+            cycle_length,
+            end_offset,
+        };
+
+        // Ends exactly a cycle length into the walk like the AoC input, here
+        // the least common multiple is the answer.
+        let aoc_like = GeneratorConfig {
+            seed: 8,
+            instruction_count: 3,
+            ghosts: vec![ghost(2, 6, 4), ghost(4, 9, 5), ghost(1, 4, 3)],
+            noise_nodes: 10,
+        };
+
+        // The least common multiple shortcut doesn't hold anymore.
+        let general = GeneratorConfig {
+            seed: 42,
+            instruction_count: 4,
+            ghosts: vec![ghost(3, 8, 2), ghost(0, 5, 4), ghost(7, 6, 1)],
+            noise_nodes: 5,
+        };
+
+        for (config, matches_lcm) in [(aoc_like, true), (general, false)] {
+            let generated = generate(&config).unwrap();
+            assert_eq!(solution::validation::validate(&generated.input), Ok(()));
+
+            let network = solution::Network::parse(&generated.input);
+            let starts = network.nodes_where(|node| node.ends_with('A'));
+            let is_end = |node: &str| node.ends_with('Z');
+
+            assert_eq!(solution::solve_part_one(&network), Ok(generated.part_one));
+            assert_eq!(
+                first_synchronized_step(&network, &starts, is_end, starts.len(), 10_000, 2),
+                generated.part_two
+            );
+            assert_eq!(
+                solution::solve_part_two(&network).ok() == generated.part_two,
+                matches_lcm
+            );
+        }
+
+        assert_eq!(
+            generate(&GeneratorConfig {
+                seed: 0,
+                instruction_count: 2,
+                ghosts: vec![ghost(0, 2, 1), ghost(0, 2, 0)],
+                noise_nodes: 0,
+            })
+            .unwrap_err(),
+            "Ghost 1 can't start on its end node."
+        );
+
+        // The product of the first 30 primes overflows an i128.
+        let primes = (2..)
+            .filter(|&number: &usize| (2..number).all(|divisor| number % divisor != 0))
+            .take(30);
+
+        assert_eq!(
+            generate(&GeneratorConfig {
+                seed: 0,
+                instruction_count: 5,
+                ghosts: primes.map(|prime| ghost(2, prime, 1)).collect(),
+                noise_nodes: 0,
+            })
+            .unwrap_err(),
+            "Can't tell the answer of part two since the combined cycles overflow i128 or \
+             their steps overflow u64."
+        );
+    }
+
+    #[test]
+    fn partial_synchronization() {
+        use solution::{
+            generator::{generate, GeneratorConfig, GhostSpec},
+            lcm::CongruenceOverflow,
+            simulation::first_synchronized_step,
+            synchronization::{
+                first_common_end_step, first_partial_synchronization, synchronization_period,
+                GhostCycle,
+            },
+        };
+
+        const INPUT: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+33A = (33B, 33B)
+33B = (33C, 33C)
+33C = (33D, 33D)
+33D = (33E, 33E)
+33E = (33Z, 33Z)
+33Z = (33A, 33A)
+XXX = (XXX, XXX)";
+
+        let network = solution::Network::parse(INPUT);
+        let is_end = |node: &str| node.ends_with('Z');
+        let ghosts = network
+            .nodes_where(|node| node.ends_with('A'))
+            .into_iter()
+            .map(|start| GhostCycle::detect(&network, start, is_end))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ghosts[2],
+            GhostCycle {
+                start: network.index_of("33A").unwrap(),
+                tail_length: 0,
+                cycle_length: 6,
+                tail_ends: vec![],
+                cycle_ends: vec![5],
+            }
+        );
+
+        // The third ghost is only on its end node on odd steps, the first one
+        // only on even ones, so they never all meet.
+        let all = ghosts.iter().collect::<Vec<_>>();
+        assert_eq!(first_common_end_step(&all), Ok(None));
+        assert_eq!(synchronization_period(&all), Ok(None));
+
+        assert_eq!(
+            first_partial_synchronization(&ghosts, 2),
+            Ok(Some((6, vec![0, 1])))
+        );
+        assert_eq!(
+            first_common_end_step(&[&ghosts[0], &ghosts[1]]),
+            Ok(Some(6))
+        );
+        assert_eq!(
+            synchronization_period(&[&ghosts[0], &ghosts[1]]),
+            Ok(Some(6))
+        );
+
+        // The second ghost is on an end node twice per cycle of 6 steps.
+        assert_eq!(ghosts[1].cycle_ends, [3, 6]);
+        assert_eq!(first_common_end_step(&[&ghosts[1]]), Ok(Some(3)));
+        assert_eq!(synchronization_period(&[&ghosts[1]]), Ok(Some(3)));
+        assert_eq!(synchronization_period(&[&ghosts[1], &ghosts[2]]), Ok(None));
+        assert_eq!(first_common_end_step(&[&ghosts[1], &ghosts[2]]), Ok(None));
+
+        let generated = generate(&GeneratorConfig {
+            seed: 7,
+            instruction_count: 3,
+            ghosts: vec![
+                GhostSpec {
+                    tail_length: 4,
+                    cycle_length: 6,
+                    end_offset: 1,
+                },
+                GhostSpec {
+                    tail_length: 1,
+                    cycle_length: 4,
+                    end_offset: 2,
+                },
+                GhostSpec {
+                    tail_length: 9,
+                    cycle_length: 9,
+                    end_offset: 0,
+                },
+            ],
+            noise_nodes: 4,
+        })
+        .unwrap();
+
+        let network = solution::Network::parse(&generated.input);
+        let starts = network.nodes_where(|node| node.ends_with('A'));
+        let ghosts = starts
+            .iter()
+            .map(|&start| GhostCycle::detect(&network, start, is_end))
+            .collect::<Vec<_>>();
+
+        for min_synchronized in 1..=ghosts.len() {
+            assert_eq!(
+                first_partial_synchronization(&ghosts, min_synchronized)
+                    .unwrap()
+                    .map(|(step, _)| step),
+                first_synchronized_step(&network, &starts, is_end, min_synchronized, 10_000, 1)
+            );
+        }
+
+        assert_eq!(
+            first_common_end_step(&ghosts.iter().collect::<Vec<_>>()),
+            Ok(generated.part_two)
+        );
+
+        // The product of the first 30 primes overflows an i128, which doesn't
+        // mean the ghosts never meet.
+        let ghosts = (2..)
+            .filter(|&number: &u64| (2..number).all(|divisor| number % divisor != 0))
+            .take(30)
+            .map(|prime| GhostCycle {
+                start: 0,
+                tail_length: 0,
+                cycle_length: prime,
+                tail_ends: vec![],
+                cycle_ends: vec![1],
+            })
+            .collect::<Vec<_>>();
+        let all = ghosts.iter().collect::<Vec<_>>();
+
+        assert_eq!(first_common_end_step(&all), Err(CongruenceOverflow));
+        assert_eq!(synchronization_period(&all), Err(CongruenceOverflow));
+        assert_eq!(first_common_end_step(&all[..5]), Ok(Some(1)));
     }
 }
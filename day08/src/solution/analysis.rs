@@ -0,0 +1,219 @@
+use std::fmt::Display;
+
+use super::Network;
+
+/// Structural report about the network and the walks through it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Analysis<'a> {
+    /// Strongly connected components in reverse topological order, every
+    /// node is part of exactly one component.
+    pub components: Vec<Vec<&'a str>>,
+    pub starts: Vec<StartAnalysis<'a>>,
+    /// Nodes which can't be reached from any start node.
+    pub dead_nodes: Vec<&'a str>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StartAnalysis<'a> {
+    pub start: &'a str,
+    /// Every node reachable with freely chosen instructions.
+    pub reachable: Vec<&'a str>,
+    /// End nodes the walk following the actual instructions ever hits.
+    pub ends_hit: Vec<&'a str>,
+}
+
+impl Display for Analysis<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let trivial_components = self
+            .components
+            .iter()
+            .filter(|component| component.len() == 1)
+            .count();
+
+        writeln!(
+            f,
+            "{} strongly connected components ({trivial_components} with a single node)",
+            self.components.len()
+        )?;
+
+        for component in self
+            .components
+            .iter()
+            .filter(|component| component.len() > 1)
+        {
+            writeln!(f, "  {} nodes: {}", component.len(), component.join(" "))?;
+        }
+
+        for start in &self.starts {
+            writeln!(
+                f,
+                "{} reaches {} nodes and hits the end nodes [{}]",
+                start.start,
+                start.reachable.len(),
+                start.ends_hit.join(" ")
+            )?;
+        }
+
+        write!(
+            f,
+            "{} dead nodes: {}",
+            self.dead_nodes.len(),
+            self.dead_nodes.join(" ")
+        )
+    }
+}
+
+/// Analyzes the network for the given start and end nodes.
+pub fn analyze<'a>(
+    network: &Network<'a>,
+    starts: &[usize],
+    is_end: impl Fn(&str) -> bool,
+) -> Analysis<'a> {
+    let names = |nodes: &[usize]| nodes.iter().map(|&node| network.names[node]).collect();
+
+    let components = strongly_connected_components(network)
+        .iter()
+        .map(|component| names(component))
+        .collect();
+
+    let mut alive = vec![false; network.names.len()];
+
+    let starts = starts
+        .iter()
+        .map(|&start| {
+            let reachable = reachable_from(network, start);
+
+            for &node in &reachable {
+                alive[node] = true;
+            }
+
+            StartAnalysis {
+                start: network.names[start],
+                reachable: names(&reachable),
+                ends_hit: names(&ends_hit(network, start, &is_end)),
+            }
+        })
+        .collect();
+
+    let dead_nodes = (0..network.names.len())
+        .filter(|&node| !alive[node])
+        .map(|node| network.names[node])
+        .collect();
+
+    Analysis {
+        components,
+        starts,
+        dead_nodes,
+    }
+}
+
+/// Nodes an instruction of the alphabet can lead to from the node.
+fn successors<'n>(network: &'n Network, node: usize) -> &'n [usize] {
+    let connections = &network.connections[node];
+    &connections[..connections.len().min(network.alphabet.symbols.len())]
+}
+
+/// Tarjan's algorithm without recursion since paths through the network can
+/// be longer than the stack allows.
+pub fn strongly_connected_components(network: &Network) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let node_count = network.names.len();
+    let mut order = vec![UNVISITED; node_count];
+    let mut low_link = vec![0; node_count];
+    let mut on_stack = vec![false; node_count];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut next_order = 0;
+
+    for root in 0..node_count {
+        if order[root] != UNVISITED {
+            continue;
+        }
+
+        // Each frame is a node and the index of the next successor to visit.
+        let mut frames = vec![(root, 0)];
+
+        while let Some(&(node, next_successor)) = frames.last() {
+            if next_successor == 0 {
+                order[node] = next_order;
+                low_link[node] = next_order;
+                next_order += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(&successor) = successors(network, node).get(next_successor) {
+                frames.last_mut().unwrap().1 += 1;
+
+                if order[successor] == UNVISITED {
+                    frames.push((successor, 0));
+                } else if on_stack[successor] {
+                    low_link[node] = low_link[node].min(order[successor]);
+                }
+
+                continue;
+            }
+
+            frames.pop();
+
+            if let Some(&(parent, _)) = frames.last() {
+                low_link[parent] = low_link[parent].min(low_link[node]);
+            }
+
+            if low_link[node] == order[node] {
+                let mut component = vec![];
+
+                loop {
+                    let member = stack.pop().unwrap();
+                    on_stack[member] = false;
+                    component.push(member);
+
+                    if member == node {
+                        break;
+                    }
+                }
+
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Every node reachable from the start (including itself) with freely chosen
+/// instructions in ascending index order.
+pub fn reachable_from(network: &Network, start: usize) -> Vec<usize> {
+    let mut visited = vec![false; network.names.len()];
+    let mut pending = vec![start];
+    visited[start] = true;
+
+    while let Some(node) = pending.pop() {
+        for &successor in successors(network, node) {
+            if !visited[successor] {
+                visited[successor] = true;
+                pending.push(successor);
+            }
+        }
+    }
+
+    (0..visited.len()).filter(|&node| visited[node]).collect()
+}
+
+/// End nodes the walk from the start hits at any step, found by walking until
+/// the same node is reached at the same instruction index again.
+pub fn ends_hit(network: &Network, start: usize, is_end: impl Fn(&str) -> bool) -> Vec<usize> {
+    let mut ends = vec![];
+
+    for step in network.walk(start).until_repeat() {
+        if is_end(step.node) {
+            ends.push(network.indices[step.node]);
+        }
+    }
+
+    ends.sort_unstable();
+    ends.dedup();
+    ends
+}
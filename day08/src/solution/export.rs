@@ -0,0 +1,88 @@
+use std::{collections::HashSet, fmt::Write};
+
+use super::{Instruction, Network};
+
+/// Renders the node network as a graphviz DOT graph.
+///
+/// Every node gets an edge labeled with the instruction symbol (`L` and `R` by
+/// default) to each of its connected points, nodes ending in `A` are filled
+/// green and nodes ending in `Z` are filled red. If a ghost is given, the edges
+/// it takes from that node until its walk starts to repeat itself are drawn
+/// thick and blue.
+pub fn to_dot(network: &Network, ghost: Option<&str>) -> Result<String, String> {
+    let ghost_edges = match ghost {
+        Some(start) => walk_until_repeating(network, start)?,
+        None => HashSet::new(),
+    };
+
+    let mut names = network.names.clone();
+    names.sort_unstable();
+
+    let mut dot = String::from("digraph network {\n");
+
+    for name in &names {
+        let attributes = if name.ends_with('A') {
+            " [style=filled, fillcolor=palegreen]"
+        } else if name.ends_with('Z') {
+            " [style=filled, fillcolor=lightcoral]"
+        } else {
+            ""
+        };
+
+        writeln!(dot, "    {}{attributes};", quote(name)).unwrap();
+    }
+
+    for name in &names {
+        let connected_points = &network.connections[network.indices[name]];
+
+        for (instruction, &target) in network.alphabet.instructions().zip(connected_points) {
+            let target = network.names[target];
+
+            let highlight = if ghost_edges.contains(&(*name, instruction)) {
+                ", color=blue, penwidth=3"
+            } else {
+                ""
+            };
+
+            writeln!(
+                dot,
+                "    {} -> {} [label={}{highlight}];",
+                quote(name),
+                quote(target),
+                quote(&instruction.to_string())
+            )
+            .unwrap();
+        }
+    }
+
+    dot.push_str("}\n");
+
+    Ok(dot)
+}
+
+/// Quotes the DOT identifier, escaping the characters with a special meaning
+/// inside quotes so any node name or instruction symbol is valid.
+fn quote(identifier: &str) -> String {
+    format!(
+        "\"{}\"",
+        identifier.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// Follows the instructions from the start until the same node is reached at
+/// the same instruction index again and returns every taken edge as the pair
+/// of the node it starts at and the followed instruction.
+fn walk_until_repeating<'a>(
+    network: &Network<'a>,
+    start: &str,
+) -> Result<HashSet<(&'a str, Instruction)>, String> {
+    let start = network
+        .index_of(start)
+        .ok_or_else(|| format!("Node `{start}` is not defined."))?;
+
+    Ok(network
+        .walk(start)
+        .until_repeat()
+        .map(|step| (step.node, step.instruction))
+        .collect())
+}
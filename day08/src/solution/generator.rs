@@ -0,0 +1,233 @@
+use std::{collections::HashSet, fmt::Write};
+
+use super::lcm::{combine_congruences, smallest_at_least, CongruenceOverflow};
+
+/// The path a single ghost takes through a generated network.
+///
+/// The ghost walks `tail_length` nodes before it enters a cycle of
+/// `cycle_length` nodes, the end node is the node `end_offset` steps into the
+/// cycle. The ghost is therefore on its end node at the steps
+/// `tail_length + end_offset + k * cycle_length`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GhostSpec {
+    pub tail_length: usize,
+    pub cycle_length: usize,
+    pub end_offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratorConfig {
+    pub seed: u64,
+    pub instruction_count: usize,
+    /// The first ghost starts at `AAA` and ends at `ZZZ` so it also defines
+    /// the answer of part one.
+    pub ghosts: Vec<GhostSpec>,
+    /// Nodes which no ghost ever visits.
+    pub noise_nodes: usize,
+}
+
+/// A generated puzzle input together with its answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedNetwork {
+    pub input: String,
+    pub part_one: u64,
+    /// `None` if the ghosts are never on their end nodes at the same time.
+    pub part_two: Option<u64>,
+}
+
+/// Generates an input in the AoC format whose ghosts follow the given specs.
+///
+/// A node on the path of a ghost can only lead somewhere else than the next
+/// node on the path (a decoy), if the ghost always visits it at the same
+/// instruction index. This holds for tail nodes and for cycle nodes if the
+/// cycle length is a multiple of the instruction count, otherwise both
+/// branches lead to the next node.
+pub fn generate(config: &GeneratorConfig) -> Result<GeneratedNetwork, String> {
+    if config.instruction_count == 0 {
+        return Err("At least one instruction is required.".into());
+    }
+
+    if config.ghosts.is_empty() || config.ghosts.len() > 600 {
+        return Err("Between one and 600 ghosts are supported.".into());
+    }
+
+    for (index, ghost) in config.ghosts.iter().enumerate() {
+        if ghost.cycle_length == 0 || ghost.end_offset >= ghost.cycle_length {
+            return Err(format!(
+                "Ghost {index} needs a cycle length above zero and an end offset below it."
+            ));
+        }
+
+        if ghost.tail_length + ghost.end_offset == 0 {
+            return Err(format!("Ghost {index} can't start on its end node."));
+        }
+    }
+
+    let mut random = Random::new(config.seed);
+    let mut names = NameGenerator::default();
+
+    let instructions = (0..config.instruction_count)
+        .map(|_| {
+            if random.next().is_multiple_of(2) {
+                'L'
+            } else {
+                'R'
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Every node is its name, the index of the next node on its path and the
+    // index of the instruction the ghost always follows from it, if there is
+    // such an index.
+    let mut nodes: Vec<(String, Option<usize>, Option<usize>)> = vec![];
+
+    for (ghost_index, ghost) in config.ghosts.iter().enumerate() {
+        let first = nodes.len();
+        let path_length = ghost.tail_length + ghost.cycle_length;
+        let end = ghost.tail_length + ghost.end_offset;
+        let fixed_cycle = ghost.cycle_length % config.instruction_count == 0;
+
+        for position in 0..path_length {
+            let name = match (position, ghost_index) {
+                (0, 0) => names.reserve("AAA"),
+                (0, _) => names.generate(&mut random, 'A')?,
+                (position, 0) if position == end => names.reserve("ZZZ"),
+                (position, _) if position == end => names.generate(&mut random, 'Z')?,
+                _ => names.generate(&mut random, ' ')?,
+            };
+
+            let next = if position + 1 == path_length {
+                first + ghost.tail_length
+            } else {
+                first + position + 1
+            };
+
+            let instruction_index = (position < ghost.tail_length || fixed_cycle)
+                .then_some(position % config.instruction_count);
+
+            nodes.push((name, Some(next), instruction_index));
+        }
+    }
+
+    for _ in 0..config.noise_nodes {
+        nodes.push((names.generate(&mut random, ' ')?, None, None));
+    }
+
+    let node_count = nodes.len();
+
+    let mut lines = nodes
+        .iter()
+        .map(|(name, next, instruction_index)| {
+            let mut decoy = || nodes[random.next() as usize % node_count].0.as_str();
+
+            let (left, right) = match (next, instruction_index) {
+                (Some(next), Some(index)) if instructions[*index] == 'L' => {
+                    (nodes[*next].0.as_str(), decoy())
+                }
+                (Some(next), Some(_)) => (decoy(), nodes[*next].0.as_str()),
+                (Some(next), None) => (nodes[*next].0.as_str(), nodes[*next].0.as_str()),
+                (None, _) => (decoy(), decoy()),
+            };
+
+            format!("{name} = ({left}, {right})")
+        })
+        .collect::<Vec<_>>();
+
+    for index in (1..lines.len()).rev() {
+        lines.swap(index, random.next() as usize % (index + 1));
+    }
+
+    let mut input = instructions.iter().collect::<String>();
+    input.push_str("\n\n");
+
+    for line in lines {
+        writeln!(input, "{line}").unwrap();
+    }
+
+    let first_ghost = config.ghosts[0];
+
+    Ok(GeneratedNetwork {
+        input,
+        part_one: (first_ghost.tail_length + first_ghost.end_offset) as u64,
+        part_two: first_common_end_step(&config.ghosts).map_err(|overflow| {
+            format!("Can't tell the answer of part two since the {overflow}.")
+        })?,
+    })
+}
+
+/// The first step all ghosts are on their end nodes at the same time.
+fn first_common_end_step(ghosts: &[GhostSpec]) -> Result<Option<u64>, CongruenceOverflow> {
+    let mut congruence = (0, 1);
+    let mut earliest = 0;
+
+    for ghost in ghosts {
+        let first_end = (ghost.tail_length + ghost.end_offset) as i128;
+        earliest = earliest.max(first_end);
+
+        match combine_congruences(congruence, (first_end, ghost.cycle_length as i128))? {
+            Some(combined) => congruence = combined,
+            None => return Ok(None),
+        }
+    }
+
+    smallest_at_least(congruence, earliest).map(Some)
+}
+
+/// SplitMix64, small and good enough to build reproducible networks.
+struct Random {
+    state: u64,
+}
+
+impl Random {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+}
+
+/// Hands out unique three letter names.
+#[derive(Default)]
+struct NameGenerator {
+    used: HashSet<String>,
+}
+
+impl NameGenerator {
+    fn reserve(&mut self, name: &str) -> String {
+        self.used.insert(name.to_string());
+        name.to_string()
+    }
+
+    /// Generates a new name ending in the suffix or, if the suffix is a
+    /// space, in neither `A` nor `Z`.
+    fn generate(&mut self, random: &mut Random, suffix: char) -> Result<String, String> {
+        const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        const MAX_NAMES: usize = 24 * 26 * 26;
+
+        if self.used.len() >= MAX_NAMES {
+            return Err(format!("Can't generate more than {MAX_NAMES} nodes."));
+        }
+
+        loop {
+            let mut name = (0..2)
+                .map(|_| LETTERS[random.next() as usize % LETTERS.len()] as char)
+                .collect::<String>();
+
+            name.push(match suffix {
+                ' ' => LETTERS[1 + random.next() as usize % 24] as char,
+                suffix => suffix,
+            });
+
+            if self.used.insert(name.clone()) {
+                return Ok(name);
+            }
+        }
+    }
+}
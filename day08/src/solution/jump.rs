@@ -0,0 +1,62 @@
+use super::Network;
+
+/// Precomputed transitions over the network which answer where a walker is
+/// after any amount of steps in `O(log n)` instead of simulating every step.
+pub struct JumpTable {
+    instruction_count: u64,
+    /// `partial_cycles[r][node]` is the node reached after `r` steps when
+    /// starting on `node` at the first instruction.
+    partial_cycles: Vec<Vec<usize>>,
+    /// `full_cycles[k][node]` is the node reached after `2^k` complete passes
+    /// through the instructions when starting on `node`.
+    full_cycles: Vec<Vec<usize>>,
+}
+
+impl JumpTable {
+    pub fn new(network: &Network) -> Self {
+        let mut partial_cycles = vec![(0..network.names.len()).collect::<Vec<_>>()];
+
+        for &instruction in &network.instructions {
+            let previous = partial_cycles.last().unwrap();
+
+            let next = previous
+                .iter()
+                .map(|&node| network.next_node(node, instruction))
+                .collect();
+
+            partial_cycles.push(next);
+        }
+
+        let instruction_count = network.instructions.len() as u64;
+        let needed_levels = u64::BITS - (u64::MAX / instruction_count).leading_zeros();
+
+        let mut full_cycles = vec![partial_cycles.pop().unwrap()];
+
+        for _ in 1..needed_levels {
+            let previous = full_cycles.last().unwrap();
+            let next = previous.iter().map(|&node| previous[node]).collect();
+            full_cycles.push(next);
+        }
+
+        Self {
+            instruction_count,
+            partial_cycles,
+            full_cycles,
+        }
+    }
+
+    /// The node a walker starting on `start` is on after `steps` steps.
+    pub fn position_after(&self, start: usize, steps: u64) -> usize {
+        let full_cycle_count = steps / self.instruction_count;
+        let remaining_steps = steps % self.instruction_count;
+
+        let after_full_cycles = self
+            .full_cycles
+            .iter()
+            .enumerate()
+            .filter(|(level, _)| full_cycle_count >> level & 1 == 1)
+            .fold(start, |node, (_, transitions)| transitions[node]);
+
+        self.partial_cycles[remaining_steps as usize][after_full_cycles]
+    }
+}
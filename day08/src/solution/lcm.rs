@@ -0,0 +1,234 @@
+use std::fmt::Display;
+
+/// The least common multiple doesn't fit into a `u64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LcmOverflow {
+    /// Index of the number which pushed the least common multiple past
+    /// `u64::MAX`.
+    pub index: usize,
+    /// The exact least common multiple of all numbers.
+    pub exact: BigUint,
+}
+
+impl Display for LcmOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "least common multiple overflows u64 at number {}, the exact value is {}",
+            self.index, self.exact
+        )
+    }
+}
+
+/// A combined congruence or a step which satisfies it doesn't fit into the
+/// integer types, which doesn't mean there is no such step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CongruenceOverflow;
+
+impl Display for CongruenceOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "combined cycles overflow i128 or their steps overflow u64"
+        )
+    }
+}
+
+/// Euclid's algorithm where `gcd(0, n) = n` and therefore `gcd(0, 0) = 0`.
+pub fn greatest_common_divisor(mut left: u64, mut right: u64) -> u64 {
+    while right != 0 {
+        (left, right) = (right, left % right);
+    }
+
+    left
+}
+
+/// Least common multiple of all numbers using checked `u64` arithmetic and
+/// falling back to a [`BigUint`] once it overflows.
+///
+/// A zero is a multiple of every number, so as soon as one of the numbers is
+/// zero (e. g. a ghost which starts on an end node) the result is zero.
+pub fn find_least_common_multiple(numbers: &[u64]) -> Result<u64, LcmOverflow> {
+    if numbers.is_empty() {
+        panic!("Can't get least common multiple of empty slice.");
+    }
+
+    if numbers.contains(&0) {
+        return Ok(0);
+    }
+
+    let mut least_common_multiple = 1u64;
+
+    for (index, &number) in numbers.iter().enumerate() {
+        let factor = number / greatest_common_divisor(least_common_multiple, number);
+
+        match least_common_multiple.checked_mul(factor) {
+            Some(product) => least_common_multiple = product,
+            None => {
+                let exact = numbers[index..].iter().fold(
+                    BigUint::from(least_common_multiple),
+                    |exact, &number| {
+                        let divisor = greatest_common_divisor(exact.remainder(number), number);
+                        exact.multiply(number / divisor)
+                    },
+                );
+
+                return Err(LcmOverflow { index, exact });
+            }
+        }
+    }
+
+    Ok(least_common_multiple)
+}
+
+/// Combines `t = r1 (mod m1)` and `t = r2 (mod m2)` into a single congruence
+/// `t = r (mod lcm(m1, m2))` with the chinese remainder theorem for moduli
+/// which don't have to be coprime.
+///
+/// Returns `None` if there is no such `t`.
+pub fn combine_congruences(
+    (remainder, modulus): (i128, i128),
+    (other_remainder, other_modulus): (i128, i128),
+) -> Result<Option<(i128, i128)>, CongruenceOverflow> {
+    let (divisor, inverse, _) = extended_euclid(modulus, other_modulus);
+    let difference = other_remainder - remainder;
+
+    if difference.rem_euclid(divisor) != 0 {
+        return Ok(None);
+    }
+
+    let reduced_modulus = other_modulus / divisor;
+    let factor = (difference / divisor)
+        .rem_euclid(reduced_modulus)
+        .checked_mul(inverse.rem_euclid(reduced_modulus))
+        .ok_or(CongruenceOverflow)?
+        .rem_euclid(reduced_modulus);
+
+    let combined_modulus = modulus
+        .checked_mul(reduced_modulus)
+        .ok_or(CongruenceOverflow)?;
+    let combined_remainder = modulus
+        .checked_mul(factor)
+        .and_then(|offset| remainder.checked_add(offset))
+        .ok_or(CongruenceOverflow)?
+        .rem_euclid(combined_modulus);
+
+    Ok(Some((combined_remainder, combined_modulus)))
+}
+
+/// The smallest `t >= minimum` with `t = r (mod m)`.
+pub fn smallest_at_least(
+    (remainder, modulus): (i128, i128),
+    minimum: i128,
+) -> Result<u64, CongruenceOverflow> {
+    let missing = (minimum - remainder).max(0);
+    let cycles = (missing + modulus - 1) / modulus;
+
+    cycles
+        .checked_mul(modulus)
+        .and_then(|offset| remainder.checked_add(offset))
+        .and_then(|step| u64::try_from(step).ok())
+        .ok_or(CongruenceOverflow)
+}
+
+/// Returns `(gcd, x, y)` with `a * x + b * y = gcd`.
+fn extended_euclid(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (divisor, x, y) = extended_euclid(b, a % b);
+        (divisor, y, x - a / b * y)
+    }
+}
+
+/// Arbitrary precision unsigned integer which only supports what the least
+/// common multiple needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigUint {
+    /// Little endian base `2^64` digits without trailing zeros.
+    limbs: Vec<u64>,
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        let mut big = Self { limbs: vec![value] };
+        big.normalize();
+        big
+    }
+}
+
+impl BigUint {
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    pub fn multiply(&self, factor: u64) -> Self {
+        let mut carry = 0u128;
+
+        let mut limbs = self
+            .limbs
+            .iter()
+            .map(|&limb| {
+                let product = limb as u128 * factor as u128 + carry;
+                carry = product >> 64;
+                product as u64
+            })
+            .collect::<Vec<_>>();
+
+        limbs.push(carry as u64);
+
+        let mut product = Self { limbs };
+        product.normalize();
+        product
+    }
+
+    /// Returns the quotient and the remainder of the division.
+    pub fn divide(&self, divisor: u64) -> (Self, u64) {
+        let mut remainder = 0u128;
+        let mut limbs = vec![0; self.limbs.len()];
+
+        for (index, &limb) in self.limbs.iter().enumerate().rev() {
+            let dividend = remainder << 64 | limb as u128;
+            limbs[index] = (dividend / divisor as u128) as u64;
+            remainder = dividend % divisor as u128;
+        }
+
+        let mut quotient = Self { limbs };
+        quotient.normalize();
+        (quotient, remainder as u64)
+    }
+
+    pub fn remainder(&self, divisor: u64) -> u64 {
+        self.divide(divisor).1
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut chunks = vec![];
+        let mut remaining = self.clone();
+
+        while !remaining.limbs.is_empty() {
+            let (quotient, chunk) = remaining.divide(CHUNK);
+            chunks.push(chunk);
+            remaining = quotient;
+        }
+
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((most_significant, rest)) => {
+                write!(f, "{most_significant}")?;
+
+                for chunk in rest.iter().rev() {
+                    write!(f, "{chunk:019}")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::{fmt::Display, str::FromStr};
+
+/// A comma separated list of node names which may contain `*` (any amount of
+/// characters) and `?` (exactly one character) wildcards e. g. `*A` or
+/// `AAA,BB?`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodePattern {
+    alternatives: Vec<Vec<char>>,
+}
+
+impl FromStr for NodePattern {
+    type Err = String;
+
+    fn from_str(raw_pattern: &str) -> Result<Self, Self::Err> {
+        let alternatives = raw_pattern
+            .split(',')
+            .map(str::trim)
+            .map(|alternative| alternative.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        if alternatives.iter().any(Vec::is_empty) {
+            return Err(format!(
+                "Node pattern `{raw_pattern}` contains an empty name."
+            ));
+        }
+
+        Ok(Self { alternatives })
+    }
+}
+
+impl Display for NodePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let alternatives = self
+            .alternatives
+            .iter()
+            .map(|alternative| alternative.iter().collect::<String>())
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", alternatives.join(","))
+    }
+}
+
+impl NodePattern {
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.chars().collect::<Vec<_>>();
+
+        self.alternatives
+            .iter()
+            .any(|alternative| matches_wildcards(alternative, &name))
+    }
+}
+
+fn matches_wildcards(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            matches_wildcards(&pattern[1..], name)
+                || (!name.is_empty() && matches_wildcards(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => matches_wildcards(&pattern[1..], &name[1..]),
+        (Some(expected), Some(actual)) if expected == actual => {
+            matches_wildcards(&pattern[1..], &name[1..])
+        }
+        _ => false,
+    }
+}
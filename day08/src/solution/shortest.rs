@@ -0,0 +1,96 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::{Instruction, Network};
+
+/// Outcome of searching for the shortest instruction sequence.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SearchResult {
+    Found(Vec<Instruction>),
+    /// Every reachable state was visited without finding one where all
+    /// walkers are on a target.
+    Unreachable,
+    /// The search stopped after visiting the maximum amount of states.
+    LimitExceeded,
+}
+
+/// Finds the shortest sequence of freely chosen instructions which brings a
+/// walker from every start node onto a target node at the same time.
+///
+/// This is a breadth first search over the product state of all walkers, which
+/// grows quickly with the amount of start nodes, so it gives up after visiting
+/// `max_states` states. Walkers which end up on the same node move together
+/// from then on, which is why a state is the set of currently occupied nodes.
+pub fn shortest_instructions(
+    network: &Network,
+    starts: &[usize],
+    is_target: impl Fn(&str) -> bool,
+    max_states: usize,
+) -> SearchResult {
+    let all_on_target = |state: &[usize]| state.iter().all(|&node| is_target(network.names[node]));
+
+    let mut initial_state = starts.to_vec();
+    initial_state.sort_unstable();
+    initial_state.dedup();
+
+    if all_on_target(&initial_state) {
+        return SearchResult::Found(vec![]);
+    }
+
+    let mut states = vec![initial_state.clone()];
+    let mut predecessors: Vec<Option<(usize, Instruction)>> = vec![None];
+    let mut visited = HashMap::from([(initial_state, 0)]);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(current) = queue.pop_front() {
+        for instruction in network.alphabet.instructions() {
+            let Some(mut next_state) = states[current]
+                .iter()
+                .map(|&node| network.connections[node].get(instruction.branch).copied())
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+
+            next_state.sort_unstable();
+            next_state.dedup();
+
+            if visited.contains_key(&next_state) {
+                continue;
+            }
+
+            if visited.len() >= max_states {
+                return SearchResult::LimitExceeded;
+            }
+
+            let next = states.len();
+            let found = all_on_target(&next_state);
+
+            visited.insert(next_state.clone(), next);
+            states.push(next_state);
+            predecessors.push(Some((current, instruction)));
+
+            if found {
+                return SearchResult::Found(reconstruct_instructions(&predecessors, next));
+            }
+
+            queue.push_back(next);
+        }
+    }
+
+    SearchResult::Unreachable
+}
+
+fn reconstruct_instructions(
+    predecessors: &[Option<(usize, Instruction)>],
+    mut state: usize,
+) -> Vec<Instruction> {
+    let mut instructions = vec![];
+
+    while let Some((previous, instruction)) = predecessors[state] {
+        instructions.push(instruction);
+        state = previous;
+    }
+
+    instructions.reverse();
+    instructions
+}
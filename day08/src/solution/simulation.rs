@@ -0,0 +1,148 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Barrier,
+    },
+    thread,
+};
+
+use super::Network;
+
+/// Amount of steps every thread simulates before the results are combined.
+const BLOCK_SIZE: u64 = 1 << 16;
+
+/// Moves all ghosts at once step by step until at least `min_synchronized` of
+/// them are on an end node at the same time and returns that step.
+///
+/// This is the brute force counterpart to the least common multiple shortcut
+/// of part two and gives up with `None` after `step_budget` steps. The ghosts
+/// are split evenly across `threads` threads which live for the whole
+/// simulation and meet at a barrier after every block of steps.
+pub fn first_synchronized_step(
+    network: &Network,
+    starts: &[usize],
+    is_end: impl Fn(&str) -> bool,
+    min_synchronized: usize,
+    step_budget: u64,
+    threads: usize,
+) -> Option<u64> {
+    if min_synchronized > starts.len() {
+        return None;
+    }
+
+    let is_end = network
+        .names
+        .iter()
+        .map(|name| is_end(name))
+        .collect::<Vec<_>>();
+
+    let mut positions = starts.to_vec();
+
+    if threads <= 1 {
+        let mut counts = vec![0; BLOCK_SIZE as usize];
+
+        for (block_start, block_size) in blocks(step_budget) {
+            let counts = &mut counts[..block_size];
+            counts.fill(0);
+            simulate_block(network, &is_end, &mut positions, block_start, counts);
+
+            if let Some(offset) = counts.iter().position(|&count| count >= min_synchronized) {
+                return Some(block_start + offset as u64);
+            }
+        }
+
+        return None;
+    }
+
+    let chunk_size = positions.len().div_ceil(threads).max(1);
+    let counts = (0..BLOCK_SIZE)
+        .map(|_| AtomicUsize::new(0))
+        .collect::<Vec<_>>();
+    // Every worker and the thread combining their counts.
+    let barrier = Barrier::new(positions.len().div_ceil(chunk_size) + 1);
+    let finished = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        for chunk in positions.chunks_mut(chunk_size) {
+            let (is_end, counts, barrier, finished) = (&is_end, &counts, &barrier, &finished);
+
+            scope.spawn(move || {
+                let mut chunk_counts = vec![0; BLOCK_SIZE as usize];
+
+                for (block_start, block_size) in blocks(step_budget) {
+                    let chunk_counts = &mut chunk_counts[..block_size];
+                    chunk_counts.fill(0);
+                    simulate_block(network, is_end, chunk, block_start, chunk_counts);
+
+                    for (count, chunk_count) in counts.iter().zip(chunk_counts) {
+                        count.fetch_add(*chunk_count, Ordering::Relaxed);
+                    }
+
+                    // Waits until the counts are combined and checked.
+                    barrier.wait();
+                    barrier.wait();
+
+                    if finished.load(Ordering::Relaxed) {
+                        break;
+                    }
+                }
+            });
+        }
+
+        for (block_start, block_size) in blocks(step_budget) {
+            barrier.wait();
+
+            let offset = counts[..block_size]
+                .iter()
+                .position(|count| count.load(Ordering::Relaxed) >= min_synchronized);
+
+            counts
+                .iter()
+                .for_each(|count| count.store(0, Ordering::Relaxed));
+            finished.store(offset.is_some(), Ordering::Relaxed);
+
+            barrier.wait();
+
+            if let Some(offset) = offset {
+                return Some(block_start + offset as u64);
+            }
+        }
+
+        None
+    })
+}
+
+/// The first step and the amount of steps of every block up to and including
+/// the step budget.
+fn blocks(step_budget: u64) -> impl Iterator<Item = (u64, usize)> {
+    (0..=step_budget)
+        .step_by(BLOCK_SIZE as usize)
+        .map(move |block_start| {
+            let block_size = (step_budget - block_start).min(BLOCK_SIZE - 1) + 1;
+            (block_start, block_size as usize)
+        })
+}
+
+/// Simulates the ghosts for the steps of the block and adds how many of them
+/// are on an end node before each step to the counts, one per step.
+fn simulate_block(
+    network: &Network,
+    is_end: &[bool],
+    positions: &mut [usize],
+    block_start: u64,
+    counts: &mut [usize],
+) {
+    let instruction_count = network.instructions.len() as u64;
+
+    for (step, count) in (block_start..).zip(counts) {
+        let instruction = network.instructions[(step % instruction_count) as usize];
+
+        for position in positions.iter_mut() {
+            if is_end[*position] {
+                *count += 1;
+            }
+
+            *position = network.next_node(*position, instruction);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+
+use super::{
+    lcm::{combine_congruences, smallest_at_least, CongruenceOverflow},
+    Network,
+};
+
+/// When a single ghost is on an end node, derived from its walk up to the
+/// point where it starts repeating itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    pub start: usize,
+    /// Steps before the ghost enters its cycle.
+    pub tail_length: u64,
+    pub cycle_length: u64,
+    /// Steps below `tail_length` on which the ghost is on an end node.
+    pub tail_ends: Vec<u64>,
+    /// Steps of the first pass through the cycle on which the ghost is on an
+    /// end node, they repeat every `cycle_length` steps.
+    pub cycle_ends: Vec<u64>,
+}
+
+impl GhostCycle {
+    /// Walks from the start until the same node is reached at the same
+    /// instruction index again.
+    pub fn detect(network: &Network, start: usize, is_end: impl Fn(&str) -> bool) -> Self {
+        let mut walk = network.walk(start).until_repeat();
+        let ends = walk
+            .by_ref()
+            .filter(|step| is_end(step.node))
+            .map(|step| step.step)
+            .collect::<Vec<_>>();
+        let (cycle_start, repeated_at) = walk
+            .repetition()
+            .expect("A walk through finitely many states repeats.");
+        let (tail_ends, cycle_ends) = ends
+            .into_iter()
+            .partition(|&end_step| end_step < cycle_start);
+
+        Self {
+            start,
+            tail_length: cycle_start,
+            cycle_length: repeated_at - cycle_start,
+            tail_ends,
+            cycle_ends,
+        }
+    }
+
+    pub fn is_end_at(&self, step: u64) -> bool {
+        if step < self.tail_length {
+            self.tail_ends.contains(&step)
+        } else {
+            let offset = (step - self.tail_length) % self.cycle_length;
+            self.cycle_ends.contains(&(self.tail_length + offset))
+        }
+    }
+}
+
+/// The first step all of the ghosts are on an end node at the same time,
+/// `None` if that never happens.
+pub fn first_common_end_step(ghosts: &[&GhostCycle]) -> Result<Option<u64>, CongruenceOverflow> {
+    let Some(longest_tail) = ghosts.iter().map(|ghost| ghost.tail_length).max() else {
+        return Ok(Some(0));
+    };
+
+    let all_on_end = |step| ghosts.iter().all(|ghost| ghost.is_end_at(step));
+
+    // Before every ghost is in its cycle there are only finitely many steps.
+    if let Some(step) = (0..longest_tail).find(|&step| all_on_end(step)) {
+        return Ok(Some(step));
+    }
+
+    let steps = common_end_congruences(ghosts)?
+        .into_iter()
+        .map(|congruence| smallest_at_least(congruence, longest_tail as i128))
+        .collect::<Vec<_>>();
+
+    // A step which overflows is later than every step which doesn't.
+    match steps.iter().filter_map(|step| step.ok()).min() {
+        Some(step) => Ok(Some(step)),
+        None => steps
+            .into_iter()
+            .find_map(Result::err)
+            .map_or(Ok(None), Err),
+    }
+}
+
+/// The congruences `step = remainder (mod lcm of the cycle lengths)` of the
+/// steps all of the ghosts are on an end node at once they are in their
+/// cycles, one for each combination of cycle ends which is compatible.
+fn common_end_congruences(ghosts: &[&GhostCycle]) -> Result<Vec<(i128, i128)>, CongruenceOverflow> {
+    let mut congruences = vec![(0, 1)];
+
+    for ghost in ghosts {
+        let mut combined = vec![];
+
+        for &congruence in &congruences {
+            for &end in &ghost.cycle_ends {
+                let cycle = (end as i128, ghost.cycle_length as i128);
+                combined.extend(combine_congruences(congruence, cycle)?);
+            }
+        }
+
+        congruences = combined;
+    }
+
+    Ok(congruences)
+}
+
+/// How often all of the ghosts are on an end node at the same time once they
+/// all are in their cycles, `None` if that never happens again.
+///
+/// This is the smallest shift which maps the common end steps onto
+/// themselves, i. e. the gap between consecutive common end steps if they
+/// are evenly spaced. It can be below the least common multiple of the cycle
+/// lengths if a ghost is on an end node more than once per cycle.
+pub fn synchronization_period(ghosts: &[&GhostCycle]) -> Result<Option<u64>, CongruenceOverflow> {
+    let congruences = common_end_congruences(ghosts)?;
+    let Some(&(_, modulus)) = congruences.first() else {
+        return Ok(None);
+    };
+    let remainders = congruences
+        .iter()
+        .map(|&(remainder, _)| remainder.rem_euclid(modulus))
+        .collect::<HashSet<_>>();
+
+    // The period leads from any common end step to another one, in particular
+    // from the first remainder, and divides the modulus.
+    let first = *remainders.iter().min().expect("There is a congruence.");
+    let mut shifts = remainders
+        .iter()
+        .map(|&remainder| match remainder - first {
+            0 => modulus,
+            shift => shift,
+        })
+        .filter(|&shift| modulus % shift == 0)
+        .collect::<Vec<_>>();
+    shifts.sort_unstable();
+
+    // The modulus itself always maps the remainders onto themselves.
+    let period = shifts
+        .into_iter()
+        .find(|&shift| {
+            remainders
+                .iter()
+                .all(|&remainder| remainders.contains(&((remainder + shift) % modulus)))
+        })
+        .expect("The modulus is a period.");
+
+    u64::try_from(period)
+        .map(Some)
+        .map_err(|_| CongruenceOverflow)
+}
+
+/// The first step at least `min_synchronized` of the ghosts are on an end node
+/// at the same time and the indices of those ghosts.
+///
+/// Every combination of `min_synchronized` ghosts is checked, which is fine for
+/// the handful of ghosts of the puzzle but grows quickly.
+pub fn first_partial_synchronization(
+    ghosts: &[GhostCycle],
+    min_synchronized: usize,
+) -> Result<Option<(u64, Vec<usize>)>, CongruenceOverflow> {
+    if min_synchronized > ghosts.len() {
+        return Ok(None);
+    }
+
+    let mut best: Option<(u64, Vec<usize>)> = None;
+    let mut subset = (0..min_synchronized).collect::<Vec<_>>();
+
+    loop {
+        let members = subset
+            .iter()
+            .map(|&index| &ghosts[index])
+            .collect::<Vec<_>>();
+
+        if let Some(step) = first_common_end_step(&members)? {
+            if best.as_ref().is_none_or(|(best_step, _)| step < *best_step) {
+                best = Some((step, subset.clone()));
+            }
+        }
+
+        // Advances to the next combination in lexicographic order.
+        let Some(position) = (0..min_synchronized)
+            .rfind(|&position| subset[position] < ghosts.len() - min_synchronized + position)
+        else {
+            return Ok(best);
+        };
+
+        subset[position] += 1;
+
+        for next in position + 1..min_synchronized {
+            subset[next] = subset[next - 1] + 1;
+        }
+    }
+}
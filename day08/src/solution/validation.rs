@@ -0,0 +1,234 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{try_parse_map_point, Alphabet};
+
+/// A problem with the node network that would either make a walk panic or
+/// silently produce a wrong answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ValidationError<'a> {
+    /// The first line has no instructions to follow.
+    EmptyInstructions,
+    /// A line which isn't a node definition in the format
+    /// `name = (left, right)`, or a second line which isn't empty.
+    MalformedLine { line: usize },
+    /// An instruction uses a symbol which isn't part of the alphabet.
+    UnknownInstruction { symbol: char, column: usize },
+    /// A node is used as a left or right connection but never defined.
+    UndefinedNode { name: &'a str, line: usize },
+    /// A node is defined more than once, only the last definition would be
+    /// used.
+    DuplicateNode {
+        name: &'a str,
+        line: usize,
+        first_line: usize,
+    },
+    /// A node has fewer branches than the instructions can pick.
+    MissingBranch {
+        name: &'a str,
+        line: usize,
+        branches: usize,
+        required: usize,
+    },
+    /// A node which part one starts or ends at has no definition.
+    MissingNode { name: &'static str },
+    /// The amount of nodes ending in `A` differs from the amount of nodes
+    /// ending in `Z` which part two relies on.
+    StartEndMismatch {
+        starts: Vec<(&'a str, usize)>,
+        ends: Vec<(&'a str, usize)>,
+    },
+}
+
+impl Display for ValidationError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyInstructions => write!(f, "line 1: there are no instructions"),
+            Self::MalformedLine { line: 2 } => {
+                write!(f, "line 2: expected an empty line after the instructions")
+            }
+            Self::MalformedLine { line } => write!(
+                f,
+                "line {line}: expected a node in the format `name = (left, right)`"
+            ),
+            Self::UnknownInstruction { symbol, column } => write!(
+                f,
+                "line 1, column {column}: instruction `{symbol}` is not part of the alphabet"
+            ),
+            Self::UndefinedNode { name, line } => {
+                write!(
+                    f,
+                    "line {line}: node `{name}` is referenced but never defined"
+                )
+            }
+            Self::DuplicateNode {
+                name,
+                line,
+                first_line,
+            } => write!(
+                f,
+                "line {line}: node `{name}` is already defined on line {first_line}"
+            ),
+            Self::MissingBranch {
+                name,
+                line,
+                branches,
+                required,
+            } => write!(
+                f,
+                "line {line}: node `{name}` has {branches} branches but the instructions require {required}"
+            ),
+            Self::MissingNode { name } => {
+                write!(f, "node `{name}` required by part one is never defined")
+            }
+            Self::StartEndMismatch { starts, ends } => {
+                let format_nodes = |nodes: &[(&str, usize)]| {
+                    nodes
+                        .iter()
+                        .map(|(name, line)| format!("{name} (line {line})"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+
+                write!(
+                    f,
+                    "part two needs as many start as end nodes but found {} ending in A [{}] and {} ending in Z [{}]",
+                    starts.len(),
+                    format_nodes(starts),
+                    ends.len(),
+                    format_nodes(ends)
+                )
+            }
+        }
+    }
+}
+
+/// Checks the input for malformed lines, missing instructions, undefined and
+/// duplicate nodes as well as the start and end nodes both parts rely on.
+/// Parsing an input without errors never panics.
+///
+/// Line numbers in the returned errors are one based and count the
+/// instruction line as well.
+pub fn validate(input: &str) -> Result<(), Vec<ValidationError<'_>>> {
+    validate_with_alphabet(input, &Alphabet::default())
+}
+
+/// Like [`validate`] but also checks that every instruction is part of the
+/// alphabet and every node has a branch for each used instruction.
+pub fn validate_with_alphabet<'a>(
+    input: &'a str,
+    alphabet: &Alphabet,
+) -> Result<(), Vec<ValidationError<'a>>> {
+    check(input, alphabet, true)
+}
+
+/// Like [`validate_with_alphabet`] but without the start and end nodes both
+/// parts rely on, for walks between any nodes.
+pub fn validate_structure<'a>(
+    input: &'a str,
+    alphabet: &Alphabet,
+) -> Result<(), Vec<ValidationError<'a>>> {
+    check(input, alphabet, false)
+}
+
+fn check<'a>(
+    input: &'a str,
+    alphabet: &Alphabet,
+    puzzle_requirements: bool,
+) -> Result<(), Vec<ValidationError<'a>>> {
+    let mut errors = vec![];
+    let mut required_branches = 0;
+    let instructions = input.lines().next().unwrap_or_default();
+
+    if instructions.is_empty() {
+        errors.push(ValidationError::EmptyInstructions);
+    }
+
+    for (index, symbol) in instructions.chars().enumerate() {
+        match alphabet.instruction(symbol) {
+            Some(instruction) => required_branches = required_branches.max(instruction.branch + 1),
+            None => errors.push(ValidationError::UnknownInstruction {
+                symbol,
+                column: index + 1,
+            }),
+        }
+    }
+
+    if input.lines().nth(1).is_none_or(|line| !line.is_empty()) {
+        errors.push(ValidationError::MalformedLine { line: 2 });
+    }
+
+    let mut map_points = vec![];
+
+    for (index, line) in input.lines().enumerate().skip(2) {
+        if line.is_empty() {
+            continue;
+        }
+
+        match try_parse_map_point(line) {
+            Some(map_point) => map_points.push((index + 1, map_point)),
+            None => errors.push(ValidationError::MalformedLine { line: index + 1 }),
+        }
+    }
+
+    let mut definitions = HashMap::new();
+
+    for &(line, (name, _)) in &map_points {
+        if let Some(&first_line) = definitions.get(name) {
+            errors.push(ValidationError::DuplicateNode {
+                name,
+                line,
+                first_line,
+            });
+        } else {
+            definitions.insert(name, line);
+        }
+    }
+
+    for (line, (_, connected_points)) in &map_points {
+        for &name in connected_points {
+            if !definitions.contains_key(name) {
+                errors.push(ValidationError::UndefinedNode { name, line: *line });
+            }
+        }
+    }
+
+    for (line, (name, connected_points)) in &map_points {
+        if connected_points.len() < required_branches {
+            errors.push(ValidationError::MissingBranch {
+                name,
+                line: *line,
+                branches: connected_points.len(),
+                required: required_branches,
+            });
+        }
+    }
+
+    if puzzle_requirements {
+        for name in ["AAA", "ZZZ"] {
+            if !definitions.contains_key(name) {
+                errors.push(ValidationError::MissingNode { name });
+            }
+        }
+
+        let defined_nodes_ending_in = |suffix| {
+            map_points
+                .iter()
+                .filter(|(line, (name, _))| name.ends_with(suffix) && definitions[name] == *line)
+                .map(|&(line, (name, _))| (name, line))
+                .collect::<Vec<_>>()
+        };
+
+        let starts = defined_nodes_ending_in('A');
+        let ends = defined_nodes_ending_in('Z');
+
+        if starts.len() != ends.len() {
+            errors.push(ValidationError::StartEndMismatch { starts, ends });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
@@ -0,0 +1,132 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{Instruction, Network};
+
+/// The state of a walker before it takes the next step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkStep<'a> {
+    /// Amount of steps taken so far.
+    pub step: u64,
+    /// Index into the instructions of the instruction followed next.
+    pub instruction_index: usize,
+    pub instruction: Instruction,
+    /// The node the walker is currently on.
+    pub node: &'a str,
+}
+
+impl Display for WalkStep<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.step, self.instruction_index, self.instruction, self.node
+        )
+    }
+}
+
+/// Endless iterator over every step of a walk through the network, starting
+/// with step 0 on the start node.
+///
+/// Use `take` to limit and `filter` to e. g. only look at end nodes.
+pub struct Walk<'n, 'a> {
+    network: &'n Network<'a>,
+    step: u64,
+    instruction_index: usize,
+    current_point: usize,
+}
+
+impl<'n, 'a> Walk<'n, 'a> {
+    pub(super) fn new(network: &'n Network<'a>, start: usize) -> Self {
+        Self {
+            network,
+            step: 0,
+            instruction_index: 0,
+            current_point: start,
+        }
+    }
+
+    /// Ends the walk right before the same node is reached at the same
+    /// instruction index again, since from there on it repeats forever.
+    pub fn until_repeat(self) -> UntilRepeat<'n, 'a> {
+        UntilRepeat {
+            walk: self,
+            first_visits: HashMap::new(),
+            repetition: None,
+        }
+    }
+}
+
+impl<'a> Iterator for Walk<'_, 'a> {
+    type Item = WalkStep<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instruction = self.network.instructions[self.instruction_index];
+
+        let current = WalkStep {
+            step: self.step,
+            instruction_index: self.instruction_index,
+            instruction,
+            node: self.network.names[self.current_point],
+        };
+
+        self.current_point = self.network.next_node(self.current_point, instruction);
+
+        self.instruction_index = (self.instruction_index + 1) % self.network.instructions.len();
+        self.step += 1;
+
+        Some(current)
+    }
+}
+
+/// A walk which ends before it starts repeating itself, see
+/// [`Walk::until_repeat`].
+pub struct UntilRepeat<'n, 'a> {
+    walk: Walk<'n, 'a>,
+    /// The step every node and instruction index pair was first reached at.
+    first_visits: HashMap<(&'a str, usize), u64>,
+    repetition: Option<(u64, u64)>,
+}
+
+impl UntilRepeat<'_, '_> {
+    /// The step the repeated node and instruction index were first reached at
+    /// and the step they are reached again, `None` until the walk has ended.
+    pub fn repetition(&self) -> Option<(u64, u64)> {
+        self.repetition
+    }
+}
+
+impl<'a> Iterator for UntilRepeat<'_, 'a> {
+    type Item = WalkStep<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.repetition.is_some() {
+            return None;
+        }
+
+        let step = self.walk.next()?;
+
+        if let Some(&first_visit) = self.first_visits.get(&(step.node, step.instruction_index)) {
+            self.repetition = Some((first_visit, step.step));
+            return None;
+        }
+
+        self.first_visits
+            .insert((step.node, step.instruction_index), step.step);
+
+        Some(step)
+    }
+}
+
+/// The first step of the walk from the start which is on an end node, `None`
+/// if the walk reaches the same node at the same instruction index again
+/// before, since it then repeats forever without reaching one.
+pub fn first_end_step<'a>(
+    network: &Network<'a>,
+    start: usize,
+    is_end: impl Fn(&str) -> bool,
+) -> Option<WalkStep<'a>> {
+    network
+        .walk(start)
+        .until_repeat()
+        .find(|step| is_end(step.node))
+}
@@ -0,0 +1,583 @@
+use std::{
+    hint::black_box,
+    io::{BufRead, IsTerminal, Write},
+    str::FromStr,
+    time::Instant,
+};
+
+use crate::solution::{
+    big_int::BigInt,
+    classification::{classify, Classification},
+    export::{export, Format},
+    extrapolation::extrapolate,
+    float::Float,
+    gaps::{fill_gaps, fill_report, parse_history, GapError},
+    model::predict,
+    modular::{is_prime, reconstruct, sum_modulo, DEFAULT_PRIMES},
+    number::Number,
+    polynomial::Polynomial,
+    predictor::Predictor,
+    rational::Rational,
+    render::render_pyramid,
+    sum_of_previous_and_next_values, try_parse_input, Method,
+};
+
+/// The number types histories can be extrapolated with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumberType {
+    I64,
+    I128,
+    Big,
+    Rational,
+    Float,
+}
+
+impl FromStr for NumberType {
+    type Err = String;
+
+    fn from_str(raw_type: &str) -> Result<Self, Self::Err> {
+        match raw_type {
+            "i64" => Ok(NumberType::I64),
+            "i128" => Ok(NumberType::I128),
+            "big" => Ok(NumberType::Big),
+            "rational" => Ok(NumberType::Rational),
+            "f64" => Ok(NumberType::Float),
+            _ => Err(format!(
+                "Unknown number type `{raw_type}`, expected `i64`, `i128`, `big`, \
+                 `rational` or `f64`."
+            )),
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(raw_method: &str) -> Result<Self, Self::Err> {
+        match raw_method {
+            "pyramid" => Ok(Method::Pyramid),
+            "closed-form" => Ok(Method::ClosedForm),
+            _ => Err(format!(
+                "Unknown method `{raw_method}`, expected `pyramid` or `closed-form`."
+            )),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(raw_format: &str) -> Result<Self, Self::Err> {
+        match raw_format {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "Unknown export format `{raw_format}`, expected `csv` or `json`."
+            )),
+        }
+    }
+}
+
+/// Runs the command given by the command line arguments (without the program
+/// name) against the input.
+///
+/// Without a command both parts are solved. The `--input` option reads
+/// another report than the puzzle input, missing values marked with `?` are
+/// reconstructed before any command runs (the `fill` command and `--number
+/// rational` keep them even if they aren't integers) and `--number
+/// i64|i128|big|rational|f64` picks the number type, `i64` by default.
+/// Decimal values like `1.25` need `rational` to be extrapolated exactly or
+/// `f64` to be extrapolated with rounding errors up to a tolerance. `--method
+/// pyramid|closed-form` picks how the values are predicted. With `--steps N`
+/// the previous and next `N` values of every history are printed instead of
+/// the sums, with `--show` the pyramids of the histories and with `--export
+/// csv|json` every history with its rows of differences, degree and previous
+/// and next value. Only one of them may be given.
+///
+/// The `fit` command prints the degree and the polynomial of every history
+/// with exact coefficients, `x` counts the values from zero. The `classify`
+/// command tells which histories are actually polynomial and `--strict` makes
+/// every command fail on the ones which are underdetermined. The `predict`
+/// command falls back to linear recurrences for histories which aren't
+/// polynomial and tells which model each prediction comes from. The `stream`
+/// command predicts the next value of the history read from stdin after every
+/// value. The `modular` command solves both parts modulo the `--primes`
+/// (comma separated) and reconstructs the exact sums from the residues. The
+/// `bench` command times both methods on a generated history, with big
+/// integers unless `--number` picks another type.
+pub fn run(input: &str, arguments: &[String]) -> Result<(), String> {
+    let mut arguments = Arguments::new(arguments);
+    let command = arguments.command();
+
+    let custom_input = arguments
+        .value("--input")?
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map_err(|error| format!("Couldn't read `{path}`: {error}"))
+        })
+        .transpose()?;
+
+    let input = custom_input.as_deref().unwrap_or(input);
+
+    let chosen_number_type = arguments
+        .value("--number")?
+        .map(str::parse::<NumberType>)
+        .transpose()?;
+    let number_type = chosen_number_type.unwrap_or(NumberType::I64);
+
+    let filled_input = (command != Some("fill") && input.contains('?'))
+        .then(|| fill_report(input, number_type == NumberType::Rational))
+        .transpose()?;
+    let input = filled_input.as_deref().unwrap_or(input);
+
+    let method = arguments
+        .value("--method")?
+        .map(str::parse::<Method>)
+        .transpose()?
+        .unwrap_or(Method::Pyramid);
+
+    if arguments.flag("--strict") {
+        match number_type {
+            NumberType::I64 => reject_underdetermined::<i64>(input)?,
+            NumberType::I128 => reject_underdetermined::<i128>(input)?,
+            NumberType::Big => reject_underdetermined::<BigInt>(input)?,
+            NumberType::Rational => reject_underdetermined::<Rational>(input)?,
+            NumberType::Float => reject_underdetermined::<Float>(input)?,
+        }
+    }
+
+    match command {
+        None => {
+            let steps = arguments.parsed_value::<u32>("--steps")?;
+            let show = arguments.flag("--show");
+            let format = arguments
+                .value("--export")?
+                .map(str::parse::<Format>)
+                .transpose()?;
+            arguments.finish()?;
+
+            let outputs = [
+                (steps.is_some(), "--steps"),
+                (show, "--show"),
+                (format.is_some(), "--export"),
+            ]
+            .into_iter()
+            .filter_map(|(given, name)| given.then_some(name))
+            .collect::<Vec<_>>();
+
+            if outputs.len() > 1 {
+                return Err(format!(
+                    "The options {} can't be combined, pick one output.",
+                    outputs.join(" and ")
+                ));
+            }
+
+            if show {
+                return match number_type {
+                    NumberType::I64 => show_pyramids::<i64>(input),
+                    NumberType::I128 => show_pyramids::<i128>(input),
+                    NumberType::Big => show_pyramids::<BigInt>(input),
+                    NumberType::Rational => show_pyramids::<Rational>(input),
+                    NumberType::Float => show_pyramids::<Float>(input),
+                };
+            }
+
+            if let Some(steps) = steps {
+                return match number_type {
+                    NumberType::I64 => print_extrapolations::<i64>(input, steps),
+                    NumberType::I128 => print_extrapolations::<i128>(input, steps),
+                    NumberType::Big => print_extrapolations::<BigInt>(input, steps),
+                    NumberType::Rational => print_extrapolations::<Rational>(input, steps),
+                    NumberType::Float => print_extrapolations::<Float>(input, steps),
+                };
+            }
+
+            if let Some(format) = format {
+                return match number_type {
+                    NumberType::I64 => print_export::<i64>(input, method, format),
+                    NumberType::I128 => print_export::<i128>(input, method, format),
+                    NumberType::Big => print_export::<BigInt>(input, method, format),
+                    NumberType::Rational => print_export::<Rational>(input, method, format),
+                    NumberType::Float => print_export::<Float>(input, method, format),
+                };
+            }
+
+            match number_type {
+                NumberType::I64 => solve::<i64>(input, method),
+                NumberType::I128 => solve::<i128>(input, method),
+                NumberType::Big => solve::<BigInt>(input, method),
+                NumberType::Rational => solve::<Rational>(input, method),
+                NumberType::Float => solve::<Float>(input, method),
+            }
+        }
+        Some("fill") => {
+            arguments.finish()?;
+
+            for (index, line) in input.lines().enumerate() {
+                let history_error = |error: GapError| format!("History {}: {error}", index + 1);
+                let filled = fill_gaps(&parse_history(line).map_err(history_error)?)
+                    .map_err(history_error)?;
+                let values = filled.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+                println!("{}", values.join(" "));
+            }
+
+            Ok(())
+        }
+        Some("modular") => {
+            let primes = arguments
+                .value("--primes")?
+                .map(|raw_primes| {
+                    raw_primes
+                        .split(',')
+                        .map(|raw_prime| match raw_prime.trim().parse::<u64>() {
+                            Ok(prime) if is_prime(prime) => Ok(prime),
+                            _ => Err(format!("`{raw_prime}` is not a prime.")),
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?
+                .unwrap_or(DEFAULT_PRIMES.to_vec());
+            arguments.finish()?;
+
+            solve_modulo(&try_parse_input::<BigInt>(input)?, &primes)
+        }
+        Some("stream") => {
+            arguments.finish()?;
+
+            match number_type {
+                NumberType::I64 => stream::<i64>(),
+                NumberType::I128 => stream::<i128>(),
+                NumberType::Big => stream::<BigInt>(),
+                NumberType::Rational => stream::<Rational>(),
+                NumberType::Float => stream::<Float>(),
+            }
+        }
+        Some("predict") => {
+            arguments.finish()?;
+
+            for (index, history) in try_parse_input::<BigInt>(input)?.iter().enumerate() {
+                let prediction = predict(history);
+                let previous = prediction
+                    .previous
+                    .map_or("unknown".to_string(), |value| value.to_string());
+
+                println!(
+                    "history {}: next {}, previous {previous} ({})",
+                    index + 1,
+                    prediction.next,
+                    prediction.model
+                );
+            }
+
+            Ok(())
+        }
+        Some("classify") => {
+            arguments.finish()?;
+
+            for (index, history) in try_parse_input::<BigInt>(input)?.iter().enumerate() {
+                let classification = classify(history).expect("Big integers can't overflow.");
+                println!("history {}: {classification}", index + 1);
+            }
+
+            Ok(())
+        }
+        Some("fit") => {
+            arguments.finish()?;
+
+            for history in try_parse_input::<BigInt>(input)? {
+                let polynomial = Polynomial::fit(&history);
+                let degree = polynomial
+                    .degree()
+                    .map_or("none".to_string(), |degree| degree.to_string());
+
+                println!("degree {degree}: {polynomial}");
+            }
+
+            Ok(())
+        }
+        Some("bench") => {
+            let length = arguments.parsed_value("--length")?.unwrap_or(1000);
+            let degree = arguments.parsed_value("--degree")?.unwrap_or(3);
+            let repeat = arguments.parsed_value("--repeat")?.unwrap_or(10);
+            arguments.finish()?;
+
+            // Long histories overflow `i64` long before the methods differ.
+            match chosen_number_type.unwrap_or(NumberType::Big) {
+                NumberType::I64 => bench::<i64>(length, degree, repeat),
+                NumberType::I128 => bench::<i128>(length, degree, repeat),
+                NumberType::Big => bench::<BigInt>(length, degree, repeat),
+                NumberType::Rational => bench::<Rational>(length, degree, repeat),
+                NumberType::Float => bench::<Float>(length, degree, repeat),
+            }
+        }
+        Some(unknown) => Err(format!("Unknown command `{unknown}`.")),
+    }
+}
+
+fn reject_underdetermined<T: Number>(input: &str) -> Result<(), String> {
+    let underdetermined = try_parse_input::<T>(input)?
+        .iter()
+        .enumerate()
+        .filter(|(_, history)| classify(history) == Some(Classification::Underdetermined))
+        .map(|(index, history)| {
+            format!(
+                "History {} is underdetermined, its {} values don't reach a row of zero \
+                 differences.",
+                index + 1,
+                history.len()
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if underdetermined.is_empty() {
+        Ok(())
+    } else {
+        Err(underdetermined.join("\n"))
+    }
+}
+
+fn solve<T: Number>(input: &str, method: Method) -> Result<(), String> {
+    try_parse_input::<T>(input)?;
+
+    let hint = |overflow| format!("{overflow} Try a wider type e. g. `--number big`.");
+
+    let (previous_sum, next_sum) =
+        sum_of_previous_and_next_values::<T>(input, method).map_err(hint)?;
+
+    println!("{next_sum}");
+    println!("{previous_sum}");
+    Ok(())
+}
+
+/// Prints the previous and next values of every history in chronological
+/// order separated by `|` e. g. `-9 -6 -3 | 18 21 24` for `0 3 6 9 12 15`.
+fn print_extrapolations<T: Number>(input: &str, steps: u32) -> Result<(), String> {
+    for (index, history) in try_parse_input::<T>(input)?.iter().enumerate() {
+        let values = |offsets: &mut dyn Iterator<Item = i64>| {
+            offsets
+                .map(|offset| {
+                    extrapolate(history, offset)
+                        .map(|value| value.to_string())
+                        .ok_or(format!(
+                            "Extrapolating history {} by {offset} overflows. Try a wider type \
+                             e. g. `--number big`.",
+                            index + 1
+                        ))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let previous = values(&mut (1..=steps as i64).rev().map(|step| -step))?;
+        let next = values(&mut (1..=steps as i64))?;
+
+        println!("{} | {}", previous.join(" "), next.join(" "));
+    }
+
+    Ok(())
+}
+
+fn print_export<T: Number>(input: &str, method: Method, format: Format) -> Result<(), String> {
+    let export = export(try_parse_input::<T>(input)?, method, format)
+        .map_err(|overflow| format!("{overflow} Try a wider type e. g. `--number big`."))?;
+
+    print!("{export}");
+    Ok(())
+}
+
+/// Prints both sums modulo every prime and the values closest to zero with
+/// those residues. They are exact if the sums are below half the product of
+/// the primes in absolute value, which is likely if dropping the last prime
+/// doesn't change them.
+fn solve_modulo(histories: &[Vec<BigInt>], primes: &[u64]) -> Result<(), String> {
+    let mut distinct_primes = primes.to_vec();
+    distinct_primes.sort_unstable();
+    distinct_primes.dedup();
+
+    if distinct_primes.len() != primes.len() {
+        return Err("The primes have to be distinct.".into());
+    }
+
+    for (name, backwards) in [("next", false), ("previous", true)] {
+        let residues = primes
+            .iter()
+            .map(|&prime| {
+                sum_modulo(histories, prime, backwards)
+                    .map(|residue| (residue, prime))
+                    .ok_or(format!("A history has at least {prime} values."))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (residue, prime) in &residues {
+            println!("{name} mod {prime}: {residue}");
+        }
+
+        let exact = reconstruct(&residues);
+        let stable = residues.len() > 1 && reconstruct(&residues[..residues.len() - 1]) == exact;
+
+        println!(
+            "{name}: {exact} ({})",
+            if stable {
+                "stable without the last prime"
+            } else {
+                "only exact if small enough, add primes to check"
+            }
+        );
+    }
+
+    Ok(())
+}
+
+/// Prints the pyramid of every history, the extrapolated values are
+/// highlighted if the output is a terminal.
+fn show_pyramids<T: Number>(input: &str) -> Result<(), String> {
+    let highlight = std::io::stdout().is_terminal();
+
+    for (index, history) in try_parse_input::<T>(input)?.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+
+        let pyramid = render_pyramid(history, highlight).ok_or(format!(
+            "The pyramid of history {} overflows. Try a wider type e. g. `--number big`.",
+            index + 1
+        ))?;
+
+        print!("{pyramid}");
+    }
+
+    Ok(())
+}
+
+/// Reads values from stdin and prints the prediction and degree after every
+/// value, an empty line starts a new history.
+fn stream<T: Number>() -> Result<(), String> {
+    let mut predictor = Predictor::<T>::default();
+    let mut output = std::io::stdout().lock();
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line.map_err(|error| format!("Couldn't read from stdin: {error}"))?;
+
+        if line.trim().is_empty() {
+            predictor = Predictor::default();
+            continue;
+        }
+
+        for raw_value in line.split_whitespace() {
+            let value = raw_value
+                .parse::<T>()
+                .map_err(|_| format!("`{raw_value}` isn't a valid value."))?;
+
+            let prediction = predictor.push(value).ok_or(format!(
+                "The prediction after `{raw_value}` overflows. Try a wider type e. g. \
+                 `--number big`."
+            ))?;
+
+            let degree = predictor
+                .degree()
+                .map_or("none".to_string(), |degree| degree.to_string());
+
+            writeln!(output, "{prediction} (degree {degree})")
+                .and_then(|_| output.flush())
+                .map_err(|error| format!("Couldn't write to stdout: {error}"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Times both methods on the history `1 + 2x + 3x^2 + ...` of the degree with
+/// `length` values.
+fn bench<T: Number>(length: u32, degree: u32, repeat: u32) -> Result<(), String> {
+    let history = (0..length)
+        .map(|x| {
+            (0..=degree).rev().try_fold(T::zero(), |value, power| {
+                value
+                    .try_mul(&T::from_u32(x))?
+                    .try_add(&T::from_u32(power + 1))
+            })
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or("The history overflows, try a wider type e. g. `--number big`.")?;
+
+    for method in [Method::Pyramid, Method::ClosedForm] {
+        let start = Instant::now();
+        let mut prediction = None;
+
+        for _ in 0..repeat {
+            prediction = black_box(method.predict_next_value(black_box(history.clone())));
+        }
+
+        // Giving up on an overflow is no time worth comparing.
+        match prediction {
+            Some(prediction) => println!(
+                "{method:?}: {prediction} in {:?} per history",
+                start.elapsed() / repeat.max(1)
+            ),
+            None => println!("{method:?}: overflow, try a wider type e. g. `--number big`"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Minimal parser for a command followed by `--flag` and `--option value`
+/// arguments in any order.
+struct Arguments<'a> {
+    remaining: Vec<&'a str>,
+}
+
+impl<'a> Arguments<'a> {
+    fn new(arguments: &'a [String]) -> Self {
+        Self {
+            remaining: arguments.iter().map(String::as_str).collect(),
+        }
+    }
+
+    fn command(&mut self) -> Option<&'a str> {
+        match self.remaining.first() {
+            Some(first) if !first.starts_with("--") => Some(self.remaining.remove(0)),
+            _ => None,
+        }
+    }
+
+    fn flag(&mut self, name: &str) -> bool {
+        match self.remaining.iter().position(|argument| *argument == name) {
+            Some(index) => {
+                self.remaining.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn value(&mut self, name: &str) -> Result<Option<&'a str>, String> {
+        let Some(index) = self.remaining.iter().position(|argument| *argument == name) else {
+            return Ok(None);
+        };
+
+        if index + 1 >= self.remaining.len() {
+            return Err(format!("Option `{name}` requires a value."));
+        }
+
+        self.remaining.remove(index);
+        Ok(Some(self.remaining.remove(index)))
+    }
+
+    fn parsed_value<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        self.value(name)?
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid value `{value}` for option `{name}`."))
+            })
+            .transpose()
+    }
+
+    fn finish(self) -> Result<(), String> {
+        match self.remaining.first() {
+            Some(unexpected) => Err(format!("Unexpected argument `{unexpected}`.")),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,39 +1,211 @@
 fn main() {
     let input = include_str!("../../input/Day09.txt");
-    println!("{}", solution::part_one(input));
-    println!("{}", solution::part_two(input));
+    let arguments = std::env::args().skip(1).collect::<Vec<_>>();
+
+    if arguments.is_empty() {
+        let (part_one, part_two) = solution::solve(input);
+        println!("{part_one}");
+        println!("{part_two}");
+        return;
+    }
+
+    if let Err(error) = cli::run(input, &arguments) {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
 }
 
+mod cli;
+
 mod solution {
-    fn parse_input(input: &str) -> Vec<Vec<i32>> {
+    use std::fmt::Display;
+
+    pub mod big_int;
+    pub mod classification;
+    pub mod closed_form;
+    pub mod export;
+    pub mod extrapolation;
+    pub mod float;
+    pub mod gaps;
+    pub mod model;
+    pub mod modular;
+    pub mod number;
+    pub mod polynomial;
+    pub mod predictor;
+    pub mod rational;
+    pub mod recurrence;
+    pub mod render;
+
+    use number::Number;
+
+    /// Reports which computation didn't fit into the chosen number type.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Overflow {
+        /// The difference pyramid or the prediction of the history with the
+        /// index overflowed.
+        History(usize),
+        /// The sum of the predictions overflowed.
+        Sum,
+    }
+
+    impl Display for Overflow {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Overflow::History(index) => {
+                    write!(f, "The prediction of history {} overflows.", index + 1)
+                }
+                Overflow::Sum => write!(f, "The sum of the predictions overflows."),
+            }
+        }
+    }
+
+    /// How the next value of a history is predicted, both methods agree for
+    /// exact number types. For `f64` only the pyramid treats differences within
+    /// the tolerance as zero, the closed form always uses every value.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Method {
+        /// Builds the difference pyramid like the puzzle text.
+        Pyramid,
+        /// Sums the history weighted with binomial coefficients.
+        ClosedForm,
+    }
+
+    impl Method {
+        pub fn predict_next_value<T: Number>(self, history: Vec<T>) -> Option<T> {
+            match self {
+                Method::Pyramid => predict_next_value(history),
+                Method::ClosedForm => closed_form::predict_next_value(&history),
+            }
+        }
+
+        pub fn predict_previous_and_next_value<T: Number>(self, history: Vec<T>) -> Option<(T, T)> {
+            match self {
+                Method::Pyramid => predict_previous_and_next_value(history),
+                Method::ClosedForm => closed_form::predict_previous_and_next_value(&history),
+            }
+        }
+    }
+
+    fn parse_input<T: Number>(input: &str) -> Vec<Vec<T>> {
+        try_parse_input(input).expect("AoC input should be valid")
+    }
+
+    /// Parses the report, the error names the first value which isn't a `T`.
+    pub fn try_parse_input<T: Number>(input: &str) -> Result<Vec<Vec<T>>, String> {
         input
             .lines()
-            .map(|line| {
+            .enumerate()
+            .map(|(line_index, line)| {
                 line.split_whitespace()
-                    .map(|raw_number| raw_number.parse().expect("AoC input should be valid"))
+                    .map(|raw_number| {
+                        raw_number.parse().map_err(|_| {
+                            format!("`{raw_number}` on line {} isn't valid.", line_index + 1)
+                        })
+                    })
                     .collect()
             })
             .collect()
     }
 
-    fn predict_next_value(history: Vec<i32>) -> i32 {
-        let mut differences: Vec<Vec<i32>> = vec![];
+    /// The history followed by its rows of differences up to the last row
+    /// which isn't all zeros, `None` if a difference doesn't fit into `T`.
+    pub fn difference_rows<T: Number>(history: Vec<T>) -> Option<Vec<Vec<T>>> {
+        let scale = T::scale(&history);
+        let mut differences: Vec<Vec<T>> = vec![];
         let mut last_differences = history;
 
-        while !last_differences.iter().all(|value| *value == 0) {
+        while !last_differences
+            .iter()
+            .all(|difference| difference.is_negligible(&scale))
+        {
             let new_differences = last_differences
                 .windows(2)
-                .map(|window| window[1] - window[0])
-                .collect();
+                .map(|window| window[1].try_sub(&window[0]))
+                .collect::<Option<_>>()?;
 
             differences.push(last_differences);
             last_differences = new_differences;
         }
 
-        differences
+        Some(differences)
+    }
+
+    /// Predicts the next value of the history, `None` if a difference or the
+    /// prediction doesn't fit into `T`.
+    pub fn predict_next_value<T: Number>(history: Vec<T>) -> Option<T> {
+        difference_rows(history)?
             .into_iter()
-            .map(|difference| *difference.last().unwrap())
-            .sum()
+            .try_fold(T::zero(), |sum, difference| {
+                sum.try_add(difference.last().unwrap())
+            })
+    }
+
+    /// Predicts the previous and the next value of the history in a single pass
+    /// over its pyramid, `None` if a difference or a prediction doesn't fit
+    /// into `T`.
+    ///
+    /// The next value is the sum of the last values of the rows. The previous
+    /// value is the first value of a row minus the previous value of the row
+    /// below, which unrolls to the first values of the rows with alternating
+    /// signs.
+    pub fn predict_previous_and_next_value<T: Number>(history: Vec<T>) -> Option<(T, T)> {
+        let mut previous = T::zero();
+        let mut next = T::zero();
+        let mut subtract = false;
+        let scale = T::scale(&history);
+        let mut differences = history;
+
+        while !differences
+            .iter()
+            .all(|difference| difference.is_negligible(&scale))
+        {
+            let first = differences.first().unwrap();
+
+            previous = if subtract {
+                previous.try_sub(first)?
+            } else {
+                previous.try_add(first)?
+            };
+            next = next.try_add(differences.last().unwrap())?;
+            subtract = !subtract;
+
+            differences = differences
+                .windows(2)
+                .map(|window| window[1].try_sub(&window[0]))
+                .collect::<Option<_>>()?;
+        }
+
+        Some((previous, next))
+    }
+
+    /// The sums of the previous and of the next values of all histories in
+    /// `T`, both from a single traversal of the input.
+    pub fn sum_of_previous_and_next_values<T: Number>(
+        input: &str,
+        method: Method,
+    ) -> Result<(T, T), Overflow> {
+        parse_input(input).into_iter().enumerate().try_fold(
+            (T::zero(), T::zero()),
+            |(previous_sum, next_sum), (index, history)| {
+                let (previous, next) = method
+                    .predict_previous_and_next_value(history)
+                    .ok_or(Overflow::History(index))?;
+
+                Ok((
+                    previous_sum.try_add(&previous).ok_or(Overflow::Sum)?,
+                    next_sum.try_add(&next).ok_or(Overflow::Sum)?,
+                ))
+            },
+        )
+    }
+
+    /// Solves both parts with a single traversal of the input, `main` uses it
+    /// instead of [`part_one`] and [`part_two`] which would traverse it twice.
+    pub fn solve(input: &str) -> (i64, i64) {
+        let (previous_sum, next_sum) = sum_of_previous_and_next_values(input, Method::Pyramid)
+            .unwrap_or_else(|overflow| panic!("{overflow}"));
+
+        (next_sum, previous_sum)
     }
 
     /// Implementation of the solution for AoC day 09 part one
@@ -146,8 +318,9 @@ mod solution {
     ///
     /// Analyze your OASIS report and extrapolate the next value for each
     /// history. **What is the sum of these extrapolated values?**
-    pub fn part_one(input: &str) -> i32 {
-        parse_input(input).into_iter().map(predict_next_value).sum()
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn part_one(input: &str) -> i64 {
+        solve(input).0
     }
 
     /// Implementation of the solution for AoC day 09 part two
@@ -186,14 +359,9 @@ mod solution {
     /// Analyze your OASIS report again, this time extrapolating the
     /// **previous** value for each history. **What is the sum of these
     /// extrapolated values?**
-    pub fn part_two(input: &str) -> i32 {
-        parse_input(input)
-            .into_iter()
-            .map(|mut history| {
-                history.reverse();
-                predict_next_value(history)
-            })
-            .sum()
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn part_two(input: &str) -> i64 {
+        solve(input).1
     }
 }
 
@@ -214,4 +382,630 @@ mod tests {
     fn part_two() {
         assert_eq!(solution::part_two(INPUT), 2)
     }
+
+    #[test]
+    fn big_int() {
+        use solution::big_int::BigInt;
+
+        for raw_number in ["0", "-1", "4294967296", "-123456789012345678901234567890"] {
+            assert_eq!(
+                raw_number.parse::<BigInt>().unwrap().to_string(),
+                raw_number
+            );
+        }
+
+        assert_eq!("-0".parse::<BigInt>().unwrap(), BigInt::from(0i64));
+        assert_eq!("+7".parse::<BigInt>().unwrap(), BigInt::from(7i64));
+        assert!("1.5".parse::<BigInt>().is_err());
+        assert!("-".parse::<BigInt>().is_err());
+
+        let big = "18446744073709551616".parse::<BigInt>().unwrap();
+        assert_eq!(
+            big.sub(&BigInt::from(1i64)).to_string(),
+            "18446744073709551615"
+        );
+        assert_eq!(
+            BigInt::from(1i64).sub(&big).to_string(),
+            "-18446744073709551615"
+        );
+        assert_eq!(big.add(&big.negate()), BigInt::from(0i64));
+        assert_eq!(BigInt::from(i64::MIN).to_string(), i64::MIN.to_string());
+
+        let dividend = "-123456789012345678901234567890".parse::<BigInt>().unwrap();
+        let divisor = "9876543210987".parse::<BigInt>().unwrap();
+        let (quotient, remainder) = dividend.div_rem(&divisor);
+        assert_eq!(quotient.to_string(), "-12499999886094578");
+        assert_eq!(remainder.to_string(), "-1249943839404");
+        assert_eq!(quotient.mul(&divisor).add(&remainder), dividend);
+
+        assert_eq!(
+            dividend.greatest_common_divisor(&BigInt::from(-36i64)),
+            BigInt::from(18i64)
+        );
+    }
+
+    #[test]
+    fn overflow_detection() {
+        use solution::{big_int::BigInt, sum_of_previous_and_next_values, Method, Overflow};
+
+        let input = "0 9223372036854775807\n1 2 3";
+        assert_eq!(
+            sum_of_previous_and_next_values::<i64>(input, Method::Pyramid).map(|(_, next)| next),
+            Err(Overflow::History(0))
+        );
+        assert_eq!(
+            sum_of_previous_and_next_values::<i128>(input, Method::Pyramid).map(|(_, next)| next),
+            Ok(18446744073709551614 + 4)
+        );
+
+        let input = "-9223372036854775807 -9223372036854775807\n-2 -2";
+        assert_eq!(
+            sum_of_previous_and_next_values::<i64>(input, Method::Pyramid).map(|(_, next)| next),
+            Err(Overflow::Sum)
+        );
+
+        let input = "0 170141183460469231731687303715884105727";
+        assert_eq!(
+            sum_of_previous_and_next_values::<i128>(input, Method::Pyramid).map(|(_, next)| next),
+            Err(Overflow::History(0))
+        );
+        assert_eq!(
+            sum_of_previous_and_next_values::<BigInt>(input, Method::Pyramid)
+                .map(|(_, next)| next)
+                .unwrap()
+                .to_string(),
+            "340282366920938463463374607431768211454"
+        );
+        assert_eq!(
+            sum_of_previous_and_next_values::<BigInt>(input, Method::Pyramid)
+                .map(|(previous, _)| previous)
+                .unwrap()
+                .to_string(),
+            "-170141183460469231731687303715884105727"
+        );
+    }
+
+    #[test]
+    fn closed_form_matches_pyramid() {
+        use solution::{
+            big_int::BigInt, closed_form, predict_next_value, predict_previous_and_next_value,
+        };
+
+        // SplitMix64 to get reproducible random histories without a dependency.
+        let mut state = 0x5EED_u64;
+        let mut random = move || {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut value = state;
+            value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            value ^ (value >> 31)
+        };
+
+        for _ in 0..200 {
+            let length = random() as usize % 30;
+            let degree = random() as i128 % 6;
+
+            // Polynomial histories make the pyramid stop early, arbitrary
+            // ones make it use every value.
+            let history = if random() % 2 == 0 {
+                let coefficients = (0..=degree)
+                    .map(|_| random() as i128 % 201 - 100)
+                    .collect::<Vec<_>>();
+
+                (0..length as i128)
+                    .map(|x| coefficients.iter().rev().fold(0, |value, c| value * x + c))
+                    .collect::<Vec<_>>()
+            } else {
+                (0..length)
+                    .map(|_| random() as i128 % 2_000_001 - 1_000_000)
+                    .collect()
+            };
+
+            let expected = predict_next_value(history.clone());
+            assert_eq!(closed_form::predict_next_value(&history), expected);
+
+            let previous = predict_next_value(history.iter().rev().copied().collect());
+            let both = previous.zip(expected);
+            assert_eq!(predict_previous_and_next_value(history.clone()), both);
+            assert_eq!(closed_form::predict_previous_and_next_value(&history), both);
+
+            let history = history
+                .iter()
+                .map(|value| value.to_string().parse::<BigInt>().unwrap())
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                closed_form::predict_next_value(&history).map(|value| value.to_string()),
+                expected.map(|value| value.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn closed_form_overflow() {
+        use solution::{big_int::BigInt, closed_form};
+
+        // The binomial coefficients of long histories overflow primitives
+        // even though the prediction is tiny.
+        let history = vec![1i64; 80];
+        assert_eq!(closed_form::predict_next_value(&history), None);
+
+        let history = vec![BigInt::from(1i64); 80];
+        assert_eq!(
+            closed_form::predict_next_value(&history),
+            Some(BigInt::from(1i64))
+        );
+    }
+
+    #[test]
+    fn extrapolate() {
+        use solution::{big_int::BigInt, extrapolation::extrapolate, predict_next_value};
+
+        let mut history = vec![10i64, 13, 16, 21, 30, 45];
+        assert_eq!(extrapolate(&history, 0), Some(45));
+        assert_eq!(extrapolate(&history, -1), Some(5));
+        assert_eq!(extrapolate(&[] as &[i64], 4), Some(0));
+
+        // Extending the history one value at a time gives the same values.
+        let original = history.clone();
+        for offset in 1..=20 {
+            let next = predict_next_value(history.clone()).unwrap();
+            assert_eq!(extrapolate(&original, offset), Some(next));
+            history.push(next);
+        }
+
+        let reversed = original.iter().rev().copied().collect::<Vec<_>>();
+        assert_eq!(extrapolate(&reversed, -20), extrapolate(&original, 20));
+
+        // 3x^2 + 2 far outside of the history.
+        let history = [2i64, 5, 14, 29];
+        assert_eq!(
+            extrapolate(&history, 1_000_000),
+            Some(3 * 1_000_003i64.pow(2) + 2)
+        );
+        assert_eq!(extrapolate(&history, i64::MAX), None);
+
+        // Neither the coefficient of a row below the last one nor the
+        // product before the division may overflow.
+        assert_eq!(
+            extrapolate(&[0i64, 1, 8, 27], 200_000),
+            Some(8_000_360_005_400_027)
+        );
+        assert_eq!(extrapolate(&[0i64, 1], 4_000_000_000), Some(4_000_000_001));
+
+        let history = history.map(BigInt::from);
+        assert_eq!(
+            extrapolate(&history, i64::MIN).unwrap().to_string(),
+            "255211775190703847597530955573826158594"
+        );
+    }
+
+    #[test]
+    fn fit_polynomial() {
+        use solution::{big_int::BigInt, polynomial::Polynomial};
+
+        let fit = |history: &[i64]| {
+            let polynomial = Polynomial::fit(
+                &history
+                    .iter()
+                    .map(|&value| BigInt::from(value))
+                    .collect::<Vec<_>>(),
+            );
+            (polynomial.degree(), polynomial.to_string())
+        };
+
+        assert_eq!(fit(&[0, 3, 6, 9, 12, 15]), (Some(1), "3x".into()));
+        assert_eq!(
+            fit(&[1, 3, 6, 10, 15, 21]),
+            (Some(2), "(1/2)x^2 + (3/2)x + 1".into())
+        );
+        assert_eq!(
+            fit(&[10, 13, 16, 21, 30, 45]),
+            (Some(3), "(1/3)x^3 - x^2 + (11/3)x + 10".into())
+        );
+        assert_eq!(fit(&[-7, -7, -7]), (Some(0), "-7".into()));
+        assert_eq!(fit(&[0, 0]), (None, "0".into()));
+        assert_eq!(fit(&[]), (None, "0".into()));
+        assert_eq!(fit(&[0, -1, -4, -9]), (Some(2), "-x^2".into()));
+    }
+
+    #[test]
+    fn classification() {
+        use solution::classification::{classify, Classification};
+
+        assert_eq!(
+            classify(&[10i64, 13, 16, 21, 30, 45]),
+            Some(Classification::Polynomial {
+                degree: Some(3),
+                confirmations: 2
+            })
+        );
+        assert_eq!(
+            classify(&[0i64, 0, 0]),
+            Some(Classification::Polynomial {
+                degree: None,
+                confirmations: 3
+            })
+        );
+        assert_eq!(classify(&[7i64]), Some(Classification::Underdetermined));
+        assert_eq!(
+            classify(&[1i64, 2, 4, 8, 16]),
+            Some(Classification::Underdetermined)
+        );
+        assert_eq!(
+            classify(&[] as &[i64]),
+            Some(Classification::Underdetermined)
+        );
+        assert_eq!(classify(&[i64::MIN, i64::MAX]), None);
+    }
+
+    #[test]
+    fn render_pyramid() {
+        use solution::render::render_pyramid;
+
+        assert_eq!(
+            render_pyramid(&[10i64, 13, 16, 21, 30, 45], false).unwrap(),
+            "5  10  13  16  21  30  45  68
+  5   3   3   5   9  15  23
+   -2   0   2   4   6   8
+      2   2   2   2   2
+        0   0   0   0
+"
+        );
+
+        assert_eq!(
+            render_pyramid(&[100i64, 0], false).unwrap(),
+            "200   100     0  -100
+  -100  -100  -100
+        0     0
+"
+        );
+
+        let highlighted = render_pyramid(&[1i64, 3, 6], true).unwrap();
+        assert_eq!(highlighted.matches("\x1b[1;32m").count(), 8);
+        assert!(highlighted.starts_with("\x1b[1;32m0\x1b[0m   1   3   6  \x1b[1;32m10\x1b[0m\n"));
+
+        assert_eq!(render_pyramid(&[i64::MIN, i64::MAX], false), None);
+    }
+
+    #[test]
+    fn fill_gaps() {
+        use solution::gaps::{fill_gaps, fill_report, parse_history, GapError};
+
+        let fill = |line: &str| {
+            fill_gaps(&parse_history(line)?).map(|values| {
+                values
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+        };
+
+        assert_eq!(fill("1 ? 6 10 ? 21"), Ok("1 3 6 10 15 21".into()));
+        assert_eq!(
+            fill("? ? 16 21 30 45 68"),
+            Ok("10 13 16 21 30 45 68".into())
+        );
+        assert_eq!(fill("0 ? 1 ? 2"), Ok("0 1/2 1 3/2 2".into()));
+        assert_eq!(fill("0 ? 0"), Ok("0 0 0".into()));
+        assert_eq!(fill("5 -2 7 1"), Ok("5 -2 7 1".into()));
+        assert_eq!(fill("1 ? 4"), Err(GapError::Underdetermined { known: 2 }));
+        assert_eq!(fill("? ?"), Err(GapError::Underdetermined { known: 0 }));
+        assert_eq!(fill("1 x"), Err(GapError::InvalidValue("x".into())));
+
+        assert_eq!(fill("0.5 ? 1.5 2"), Ok("1/2 1 3/2 2".into()));
+
+        assert_eq!(
+            fill_report("0 3 ? 9\n1 ? 6 10 ? 21\n", false),
+            Ok("0 3 6 9\n1 3 6 10 15 21\n".into())
+        );
+        assert_eq!(
+            fill_report("0 3 6\n0 ? 1 ? 2", false),
+            Err(
+                "History 2: The missing value at position 2 is 1/2 and not an integer, only \
+                 `--number rational` keeps it."
+                    .into()
+            )
+        );
+        assert_eq!(
+            fill_report("0 ? 1 ? 2\n1.25 ? 1.75 2", true),
+            Ok("0 1/2 1 3/2 2\n1.25 3/2 1.75 2\n".into())
+        );
+    }
+
+    #[test]
+    fn streaming_predictor() {
+        use solution::{predict_next_value, predictor::Predictor};
+
+        let history = [10i64, 13, 16, 21, 30, 45, 68, 101, 146];
+        let mut predictor = Predictor::default();
+
+        for (count, &value) in history.iter().enumerate() {
+            let prediction = predictor.push(value);
+            assert_eq!(prediction, predict_next_value(history[..=count].to_vec()));
+        }
+
+        assert_eq!(predictor.degree(), Some(3));
+
+        // Zeros first and a jump later grow the state to the whole history.
+        let mut predictor = Predictor::default();
+        for value in [0i64, 0, 0, 0] {
+            assert_eq!(predictor.push(value), Some(0));
+        }
+        assert_eq!(predictor.degree(), None);
+        assert_eq!(predictor.push(5), predict_next_value(vec![0, 0, 0, 0, 5]));
+        assert_eq!(predictor.degree(), Some(4));
+
+        // An overflow leaves the predictor unchanged.
+        let mut predictor = Predictor::default();
+        predictor.push(i64::MIN);
+        assert_eq!(predictor.push(i64::MAX), None);
+        assert_eq!(predictor.push(i64::MIN), Some(i64::MIN));
+    }
+
+    #[test]
+    fn linear_recurrence() {
+        use solution::{
+            big_int::BigInt,
+            model::{predict, Model},
+            rational::Rational,
+            recurrence::Recurrence,
+        };
+
+        let sequence = |values: &[i64]| {
+            values
+                .iter()
+                .map(|&value| Rational::from(value))
+                .collect::<Vec<_>>()
+        };
+
+        let fibonacci = sequence(&[1, 1, 2, 3, 5, 8, 13]);
+        let recurrence = Recurrence::find(&fibonacci);
+        assert_eq!(recurrence.to_string(), "s(n) = s(n-1) + s(n-2)");
+        assert_eq!(recurrence.next_value(&fibonacci), Rational::from(21));
+        assert_eq!(
+            recurrence.previous_value(&fibonacci),
+            Some(Rational::from(0))
+        );
+
+        let geometric = sequence(&[3, -6, 12, -24]);
+        assert_eq!(Recurrence::find(&geometric).to_string(), "s(n) = -2 s(n-1)");
+        assert_eq!(Recurrence::find(&sequence(&[0, 0])).to_string(), "s(n) = 0");
+        assert_eq!(Recurrence::find(&sequence(&[2, 1, 1, 3, 1])).order(), 3);
+
+        let predict = |values: &[i64]| {
+            let history = values
+                .iter()
+                .map(|&value| BigInt::from(value))
+                .collect::<Vec<_>>();
+            let prediction = predict(&history);
+
+            (
+                prediction.model.to_string(),
+                prediction.next.to_string(),
+                prediction.previous.map(|value| value.to_string()),
+            )
+        };
+
+        assert_eq!(
+            predict(&[1, 3, 6, 10, 15, 21]),
+            (
+                "polynomial of degree 2".into(),
+                "28".into(),
+                Some("0".into())
+            )
+        );
+        assert_eq!(
+            predict(&[2, 6, 18, 54, 162]),
+            (
+                "recurrence s(n) = 3 s(n-1)".into(),
+                "486".into(),
+                Some("2/3".into())
+            )
+        );
+        assert_eq!(
+            predict(&[0, 1, 1, 2, 3, 5, 8]),
+            (
+                "recurrence s(n) = s(n-1) + s(n-2)".into(),
+                "13".into(),
+                Some("1".into())
+            )
+        );
+        assert_eq!(predict(&[4, 4, 1, 9]).0, "underdetermined polynomial");
+        assert!(matches!(
+            solution::model::predict(&[BigInt::from(5i64)]).model,
+            Model::Underdetermined
+        ));
+    }
+
+    #[test]
+    fn rational_histories() {
+        use solution::{
+            extrapolation::extrapolate, rational::Rational, sum_of_previous_and_next_values,
+            try_parse_input, Method,
+        };
+
+        let input = "0.1 0.2 0.3\n1/3 2/3 1 4/3\n-0.5 .5 1.5 2.5\n5. 5 5.000";
+        assert_eq!(
+            sum_of_previous_and_next_values::<Rational>(input, Method::Pyramid)
+                .map(|(_, next)| next)
+                .map(|sum| sum.to_string()),
+            Ok("317/30".into())
+        );
+        assert_eq!(
+            sum_of_previous_and_next_values::<Rational>(input, Method::ClosedForm)
+                .map(|(previous, _)| previous)
+                .map(|sum| sum.to_string()),
+            Ok("7/2".into())
+        );
+
+        let history = &try_parse_input::<Rational>("1/2 1/4 1/8 1/16 1/32").unwrap()[0];
+        assert_eq!(extrapolate(history, 1).unwrap().to_string(), "1/32");
+
+        for invalid in ["", ".", "-", "1.2.3", "1/0", "1e5", "--1", "1.-5", "0x10"] {
+            assert!(invalid.parse::<Rational>().is_err(), "{invalid}");
+        }
+
+        assert_eq!("-0.0".parse::<Rational>(), Ok(Rational::from(0)));
+        assert_eq!("6/-4".parse::<Rational>().unwrap().to_string(), "-3/2");
+    }
+
+    #[test]
+    fn float_histories() {
+        use solution::{
+            classification::{classify, Classification},
+            extrapolation::extrapolate,
+            float::Float,
+            predict_next_value, sum_of_previous_and_next_values, try_parse_input, Method,
+        };
+
+        // Exactly the second differences aren't zero due to rounding.
+        let history = &try_parse_input::<Float>("0.1 0.2 0.3 0.4 0.5").unwrap()[0];
+        assert_eq!(
+            classify(history),
+            Some(Classification::Polynomial {
+                degree: Some(1),
+                confirmations: 3
+            })
+        );
+        assert!((extrapolate(history, 1).unwrap().0 - 0.6).abs() < 1e-12);
+        assert!((extrapolate(history, -1).unwrap().0).abs() < 1e-12);
+
+        let input = "1.5 2.25 3\n1e-12 2e-12 3e-12";
+        let sum = sum_of_previous_and_next_values::<Float>(input, Method::Pyramid)
+            .map(|(_, next)| next)
+            .unwrap();
+        assert!((sum.0 - 3.75).abs() < 1e-9);
+
+        // The tolerance grows with the values, which have larger rounding
+        // errors, and shrinks with them so tiny slopes aren't lost.
+        for scale in [1e8, 1e-12] {
+            let history = (1..=10)
+                .map(|value| Float(scale + value as f64 * scale / 1e9))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                classify(&history),
+                Some(Classification::Polynomial {
+                    degree: Some(1),
+                    confirmations: 8
+                })
+            );
+            let next = predict_next_value(history).unwrap().0;
+            assert!((next - (scale + 11.0 * scale / 1e9)).abs() < scale * 1e-12);
+        }
+
+        let history = &try_parse_input::<Float>(
+            "100000000.1 100000000.2 100000000.3 100000000.4 100000000.5 100000000.6 \
+             100000000.7 100000000.8 100000000.9 100000001.0",
+        )
+        .unwrap()[0];
+        assert!((extrapolate(history, 1).unwrap().0 - 100000001.1).abs() < 1e-6);
+
+        let history = &try_parse_input::<Float>("-1e308 1e308").unwrap()[0];
+        assert_eq!(extrapolate(history, 1), None);
+
+        for invalid in ["NaN", "inf", "-infinity", "1e400", "x"] {
+            assert!(invalid.parse::<Float>().is_err(), "{invalid}");
+        }
+
+        assert_eq!(Float(-0.0).to_string(), "0");
+        assert_eq!(Float(2.5).to_string(), "2.5");
+    }
+
+    #[test]
+    fn modular_extrapolation() {
+        use solution::{
+            big_int::BigInt,
+            modular::{is_prime, next_value_modulo, reconstruct, sum_modulo, DEFAULT_PRIMES},
+            sum_of_previous_and_next_values, try_parse_input, Method,
+        };
+
+        assert!(DEFAULT_PRIMES.iter().all(|&prime| is_prime(prime)));
+        assert!(is_prime(2) && is_prime(998_244_353) && is_prime(u64::MAX - 58));
+        assert!(!is_prime(1) && !is_prime(561) && !is_prime(u64::MAX));
+
+        let values = |raw: &str| try_parse_input::<BigInt>(raw).unwrap();
+
+        // Histories longer than 60 values have huge binomial coefficients and
+        // values far outside of `i128`.
+        let input = (0..3)
+            .map(|offset| {
+                (0..80)
+                    .map(|x| format!("{}", (x + offset) * 37i128.pow(18) - x * x))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .chain(["5 -2 7 1 -8 3 9 -4 2 11 -6 1 8 -3 5 -7 2 9 -1 4 6 -9 3 7 -5".into()])
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let histories = values(&input);
+        for (backwards, expected) in [
+            (
+                false,
+                sum_of_previous_and_next_values::<BigInt>(&input, Method::Pyramid)
+                    .map(|(_, next)| next),
+            ),
+            (
+                true,
+                sum_of_previous_and_next_values::<BigInt>(&input, Method::Pyramid)
+                    .map(|(previous, _)| previous),
+            ),
+        ] {
+            let residues = DEFAULT_PRIMES
+                .iter()
+                .map(|&prime| (sum_modulo(&histories, prime, backwards).unwrap(), prime))
+                .collect::<Vec<_>>();
+
+            assert_eq!(Ok(reconstruct(&residues)), expected);
+        }
+
+        assert_eq!(next_value_modulo(&values("1 2 3")[0], 3), None);
+        assert_eq!(next_value_modulo(&values("-1 -2")[0], 7), Some(4));
+        assert_eq!(reconstruct(&[(6, 7), (10, 11)]), BigInt::from(-1i64));
+        assert_eq!(reconstruct(&[(3, 7), (3, 11)]), BigInt::from(3i64));
+        assert_eq!(reconstruct(&[]), BigInt::from(0i64));
+    }
+
+    #[test]
+    fn export() {
+        use solution::{
+            export::{export, Format},
+            try_parse_input, Method, Overflow,
+        };
+
+        let histories = try_parse_input::<i64>("0 3 6\n0 0\n1 4").unwrap();
+        assert_eq!(
+            export(histories.clone(), Method::Pyramid, Format::Csv),
+            Ok("history,degree,underdetermined,previous,next,row,values\n\
+                1,1,false,-3,9,0,0 3 6\n\
+                1,1,false,-3,9,1,3 3\n\
+                2,,false,0,0,0,0 0\n\
+                3,,true,-2,7,0,1 4\n\
+                3,,true,-2,7,1,3\n"
+                .into())
+        );
+        assert_eq!(
+            export(histories, Method::ClosedForm, Format::Json),
+            Ok("[\n  \
+                {\"history\":1,\"degree\":1,\"underdetermined\":false,\"previous\":\"-3\",\
+                 \"next\":\"9\",\"rows\":[[\"0\",\"3\",\"6\"],[\"3\",\"3\"]]},\n  \
+                {\"history\":2,\"degree\":null,\"underdetermined\":false,\"previous\":\"0\",\
+                 \"next\":\"0\",\"rows\":[[\"0\",\"0\"]]},\n  \
+                {\"history\":3,\"degree\":null,\"underdetermined\":true,\"previous\":\"-2\",\
+                 \"next\":\"7\",\"rows\":[[\"1\",\"4\"],[\"3\"]]}\n]\n"
+                .into())
+        );
+
+        assert_eq!(
+            export::<i64>(vec![], Method::Pyramid, Format::Json),
+            Ok("[]\n".into())
+        );
+        assert_eq!(
+            export(vec![vec![1, i64::MIN]], Method::Pyramid, Format::Csv),
+            Err(Overflow::History(0))
+        );
+    }
 }
@@ -0,0 +1,334 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+/// Arbitrary precision signed integer for histories whose values or
+/// differences don't fit into a primitive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    /// Little endian base `2^32` digits without trailing zeros, zero has no
+    /// digits and is never negative.
+    magnitude: Vec<u32>,
+}
+
+/// The largest power of ten fitting into a digit, used to convert from and to
+/// decimal in chunks.
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        let magnitude = BigInt::from(value.unsigned_abs()).magnitude;
+        Self::new(value < 0, magnitude)
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(value: u64) -> Self {
+        Self::new(false, vec![value as u32, (value >> 32) as u32])
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    fn from_str(raw_number: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match raw_number.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, raw_number.strip_prefix('+').unwrap_or(raw_number)),
+        };
+
+        if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+            return Err(format!("`{raw_number}` is not an integer."));
+        }
+
+        let mut magnitude = vec![];
+        let first_chunk_length = match digits.len() % DECIMAL_CHUNK_DIGITS {
+            0 => DECIMAL_CHUNK_DIGITS,
+            length => length,
+        };
+
+        let mut remaining = digits;
+        let mut chunk_length = first_chunk_length;
+
+        while !remaining.is_empty() {
+            let (chunk, rest) = remaining.split_at(chunk_length);
+            let chunk = chunk.parse::<u32>().unwrap();
+            let scale = 10u32.pow(chunk_length as u32);
+
+            multiply_add_small(&mut magnitude, scale, chunk);
+
+            remaining = rest;
+            chunk_length = DECIMAL_CHUNK_DIGITS;
+        }
+
+        Ok(Self::new(negative, magnitude))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut chunks = vec![];
+        let mut remaining = self.magnitude.clone();
+
+        while !remaining.is_empty() {
+            chunks.push(divide_small(&mut remaining, DECIMAL_CHUNK));
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((most_significant, rest)) => {
+                write!(f, "{most_significant}")?;
+
+                for chunk in rest.iter().rev() {
+                    write!(f, "{chunk:0width$}", width = DECIMAL_CHUNK_DIGITS)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl BigInt {
+    fn new(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+
+        Self {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        if self.negative == other.negative {
+            return Self::new(
+                self.negative,
+                add_magnitudes(&self.magnitude, &other.magnitude),
+            );
+        }
+
+        match compare_magnitudes(&self.magnitude, &other.magnitude) {
+            Ordering::Less => Self::new(
+                other.negative,
+                subtract_magnitudes(&other.magnitude, &self.magnitude),
+            ),
+            _ => Self::new(
+                self.negative,
+                subtract_magnitudes(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+
+    pub fn negate(&self) -> Self {
+        Self::new(!self.negative, self.magnitude.clone())
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.negate())
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut product = vec![0u32; self.magnitude.len() + other.magnitude.len()];
+
+        for (index, &digit) in self.magnitude.iter().enumerate() {
+            let mut carry = 0u64;
+
+            for (other_index, &other_digit) in other.magnitude.iter().enumerate() {
+                let total =
+                    product[index + other_index] as u64 + digit as u64 * other_digit as u64 + carry;
+
+                product[index + other_index] = total as u32;
+                carry = total >> 32;
+            }
+
+            product[index + other.magnitude.len()] = carry as u32;
+        }
+
+        Self::new(self.negative != other.negative, product)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::new(false, self.magnitude.clone())
+    }
+
+    /// The quotient rounded towards zero and the remainder, which has the sign
+    /// of `self`.
+    ///
+    /// # Panics
+    ///
+    /// If the divisor is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        assert!(!divisor.magnitude.is_empty(), "Division by zero.");
+
+        // Shifts the bits of the dividend into the remainder one at a time and
+        // subtracts the divisor whenever it fits.
+        let mut quotient = vec![0u32; self.magnitude.len()];
+        let mut remainder: Vec<u32> = vec![];
+
+        for bit in (0..self.magnitude.len() * 32).rev() {
+            let mut carry = self.magnitude[bit / 32] >> (bit % 32) & 1;
+
+            for digit in remainder.iter_mut() {
+                let shifted_out = *digit >> 31;
+                *digit = *digit << 1 | carry;
+                carry = shifted_out;
+            }
+
+            if carry != 0 {
+                remainder.push(carry);
+            }
+
+            if compare_magnitudes(&remainder, &divisor.magnitude) != Ordering::Less {
+                remainder = subtract_magnitudes(&remainder, &divisor.magnitude);
+
+                while remainder.last() == Some(&0) {
+                    remainder.pop();
+                }
+
+                quotient[bit / 32] |= 1 << (bit % 32);
+            }
+        }
+
+        (
+            Self::new(self.negative != divisor.negative, quotient),
+            Self::new(self.negative, remainder),
+        )
+    }
+
+    /// The non negative greatest common divisor, zero only if both are zero.
+    pub fn greatest_common_divisor(&self, other: &Self) -> Self {
+        let (mut left, mut right) = (self.abs(), other.abs());
+
+        while !right.magnitude.is_empty() {
+            let remainder = left.div_rem(&right).1;
+            left = right;
+            right = remainder;
+        }
+
+        left
+    }
+
+    /// Divides by a small divisor, rounding towards zero.
+    pub fn div_small(&self, divisor: u32) -> Self {
+        let mut quotient = self.magnitude.clone();
+        divide_small(&mut quotient, divisor);
+        Self::new(self.negative, quotient)
+    }
+
+    /// The remainder in `0..modulus`, also for negative numbers.
+    pub fn rem_euclid_u64(&self, modulus: u64) -> u64 {
+        let remainder = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0u128, |remainder, &digit| {
+                (remainder << 32 | digit as u128) % modulus as u128
+            }) as u64;
+
+        if self.negative && remainder != 0 {
+            modulus - remainder
+        } else {
+            remainder
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitudes(&self.magnitude, &other.magnitude),
+            (true, true) => compare_magnitudes(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+fn compare_magnitudes(left: &[u32], right: &[u32]) -> Ordering {
+    left.len()
+        .cmp(&right.len())
+        .then_with(|| left.iter().rev().cmp(right.iter().rev()))
+}
+
+fn add_magnitudes(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(left.len().max(right.len()) + 1);
+    let mut carry = 0u64;
+
+    for index in 0..left.len().max(right.len()) {
+        let total =
+            *left.get(index).unwrap_or(&0) as u64 + *right.get(index).unwrap_or(&0) as u64 + carry;
+
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+
+    sum.push(carry as u32);
+    sum
+}
+
+/// Subtracts the smaller magnitude `right` from `left`.
+fn subtract_magnitudes(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(left.len());
+    let mut borrow = 0i64;
+
+    for (index, &digit) in left.iter().enumerate() {
+        let mut total = digit as i64 - *right.get(index).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+
+        if total < 0 {
+            total += 1 << 32;
+            borrow = 1;
+        }
+
+        difference.push(total as u32);
+    }
+
+    difference
+}
+
+fn multiply_add_small(magnitude: &mut Vec<u32>, factor: u32, summand: u32) {
+    let mut carry = summand as u64;
+
+    for digit in magnitude.iter_mut() {
+        let total = *digit as u64 * factor as u64 + carry;
+        *digit = total as u32;
+        carry = total >> 32;
+    }
+
+    if carry != 0 {
+        magnitude.push(carry as u32);
+    }
+}
+
+/// Divides the magnitude in place and returns the remainder.
+fn divide_small(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+
+    for digit in magnitude.iter_mut().rev() {
+        let dividend = remainder << 32 | *digit as u64;
+        *digit = (dividend / divisor as u64) as u32;
+        remainder = dividend % divisor as u64;
+    }
+
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+
+    remainder as u32
+}
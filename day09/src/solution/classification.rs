@@ -0,0 +1,55 @@
+use std::fmt::Display;
+
+use super::{difference_rows, number::Number};
+
+/// Whether the difference pyramid of a history actually shows a polynomial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classification {
+    /// A row of differences is all zeros before the pyramid runs out of
+    /// values, `confirmations` is the amount of values in that row. The degree
+    /// is `None` if the history itself is all zeros.
+    Polynomial {
+        degree: Option<usize>,
+        confirmations: usize,
+    },
+    /// The pyramid only ends because its last row is empty. The fitted
+    /// polynomial has degree `length - 1` and passes through every value, so
+    /// any history fits and its prediction is meaningless.
+    Underdetermined,
+}
+
+impl Display for Classification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Classification::Polynomial {
+                degree: Some(degree),
+                confirmations,
+            } => write!(
+                f,
+                "polynomial of degree {degree} confirmed by {confirmations} zero differences"
+            ),
+            Classification::Polynomial {
+                degree: None,
+                confirmations,
+            } => write!(f, "all {confirmations} values are zero"),
+            Classification::Underdetermined => {
+                write!(f, "underdetermined, the fit uses every value")
+            }
+        }
+    }
+}
+
+/// Classifies the history, `None` if a difference doesn't fit into `T`.
+pub fn classify<T: Number>(history: &[T]) -> Option<Classification> {
+    let rows = difference_rows(history.to_vec())?.len();
+    let confirmations = history.len() - rows;
+
+    Some(if confirmations == 0 {
+        Classification::Underdetermined
+    } else {
+        Classification::Polynomial {
+            degree: rows.checked_sub(1),
+            confirmations,
+        }
+    })
+}
@@ -0,0 +1,80 @@
+use super::number::Number;
+
+/// Predicts the next value of the history without building the difference
+/// pyramid, `None` if an intermediate value doesn't fit into `T`.
+///
+/// A history of `n` values is extrapolated with the polynomial of degree below
+/// `n` through all of them, its `n`-th differences are zero. Solving
+/// `sum((-1)^(n - i) * C(n, i) * h_i for i in 0..=n) = 0` for `h_n` gives
+///
+/// ```text
+/// h_n = sum((-1)^(n - 1 - i) * C(n, i) * h_i for i in 0..n)
+/// ```
+///
+/// This is the same value the pyramid predicts, as the pyramid only stops
+/// early once the higher differences are zero anyway. The binomial
+/// coefficients grow quickly though, so primitives overflow for histories of
+/// about 60 values even if the prediction itself is small.
+pub fn predict_next_value<T: Number>(history: &[T]) -> Option<T> {
+    let length = history.len() as u32;
+    let mut coefficient = T::from_u32(1);
+    let mut prediction = T::zero();
+
+    for (index, value) in (0..length).zip(history) {
+        let term = coefficient.try_mul(value)?;
+
+        prediction = if (length - 1 - index).is_multiple_of(2) {
+            prediction.try_add(&term)?
+        } else {
+            prediction.try_sub(&term)?
+        };
+
+        // C(n, i + 1) = C(n, i) * (n - i) / (i + 1) where the division is exact.
+        coefficient = coefficient
+            .try_mul(&T::from_u32(length - index))?
+            .try_div_small(index + 1)?;
+    }
+
+    Some(prediction)
+}
+
+/// Predicts the previous and the next value of the history in a single pass,
+/// `None` if an intermediate value doesn't fit into `T`.
+///
+/// Solving the same equation for `h_-1` instead gives
+///
+/// ```text
+/// h_-1 = sum((-1)^i * C(n, i + 1) * h_i for i in 0..n)
+/// ```
+///
+/// so both sums share the binomial coefficients.
+pub fn predict_previous_and_next_value<T: Number>(history: &[T]) -> Option<(T, T)> {
+    let length = history.len() as u32;
+    let mut coefficient = T::from_u32(1);
+    let mut previous = T::zero();
+    let mut next = T::zero();
+
+    for (index, value) in (0..length).zip(history) {
+        let next_term = coefficient.try_mul(value)?;
+
+        next = if (length - 1 - index).is_multiple_of(2) {
+            next.try_add(&next_term)?
+        } else {
+            next.try_sub(&next_term)?
+        };
+
+        coefficient = coefficient
+            .try_mul(&T::from_u32(length - index))?
+            .try_div_small(index + 1)?;
+
+        let previous_term = coefficient.try_mul(value)?;
+
+        previous = if index.is_multiple_of(2) {
+            previous.try_add(&previous_term)?
+        } else {
+            previous.try_sub(&previous_term)?
+        };
+    }
+
+    Some((previous, next))
+}
@@ -0,0 +1,152 @@
+use std::fmt::Write;
+
+use super::{
+    classification::{classify, Classification},
+    difference_rows,
+    number::Number,
+    Method, Overflow,
+};
+
+/// The formats histories can be exported as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+/// Everything exported about one history.
+struct Record<T> {
+    /// The history followed by its rows of differences up to the last row
+    /// which isn't all zeros, just the history if it is all zeros.
+    rows: Vec<Vec<T>>,
+    classification: Classification,
+    previous: T,
+    next: T,
+}
+
+impl<T> Record<T> {
+    /// The detected degree, `None` for histories which are all zeros or
+    /// underdetermined.
+    fn degree(&self) -> Option<usize> {
+        match self.classification {
+            Classification::Polynomial { degree, .. } => degree,
+            Classification::Underdetermined => None,
+        }
+    }
+
+    fn is_underdetermined(&self) -> bool {
+        self.classification == Classification::Underdetermined
+    }
+}
+
+/// Exports every history with its rows of differences, its detected degree
+/// and its previous and next value.
+///
+/// Both formats have the same fields: `history` (counted from one), `degree`
+/// (empty or `null` for histories which are all zeros or underdetermined),
+/// `underdetermined` (whether no row of zeros confirms the degree, see
+/// [`Classification::Underdetermined`]), `previous`, `next` and the rows, the
+/// first of which is the history itself. The CSV has a line per row with the
+/// row number and its values separated by spaces, the JSON an object per
+/// history with the rows as arrays. Values are JSON strings as neither
+/// fractions nor big integers are safe JSON numbers.
+pub fn export<T: Number>(
+    histories: Vec<Vec<T>>,
+    method: Method,
+    format: Format,
+) -> Result<String, Overflow> {
+    let records = histories
+        .into_iter()
+        .enumerate()
+        .map(|(index, history)| {
+            let (previous, next) = method
+                .predict_previous_and_next_value(history.clone())
+                .ok_or(Overflow::History(index))?;
+            let classification = classify(&history).ok_or(Overflow::History(index))?;
+            let mut rows = difference_rows(history.clone()).ok_or(Overflow::History(index))?;
+
+            if rows.is_empty() {
+                rows.push(history);
+            }
+
+            Ok(Record {
+                rows,
+                classification,
+                previous,
+                next,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(match format {
+        Format::Csv => csv(&records),
+        Format::Json => json(&records),
+    })
+}
+
+fn csv<T: Number>(records: &[Record<T>]) -> String {
+    let mut output = String::from("history,degree,underdetermined,previous,next,row,values\n");
+
+    for (index, record) in records.iter().enumerate() {
+        let degree = record
+            .degree()
+            .map_or(String::new(), |degree| degree.to_string());
+
+        for (row_index, row) in record.rows.iter().enumerate() {
+            let values = row.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+            writeln!(
+                output,
+                "{},{degree},{},{},{},{row_index},{}",
+                index + 1,
+                record.is_underdetermined(),
+                record.previous,
+                record.next,
+                values.join(" ")
+            )
+            .unwrap();
+        }
+    }
+
+    output
+}
+
+fn json<T: Number>(records: &[Record<T>]) -> String {
+    let objects = records
+        .iter()
+        .enumerate()
+        .map(|(index, record)| {
+            let degree = record
+                .degree()
+                .map_or("null".to_string(), |degree| degree.to_string());
+            let rows = record
+                .rows
+                .iter()
+                .map(|row| {
+                    let values = row
+                        .iter()
+                        .map(|value| format!("\"{value}\""))
+                        .collect::<Vec<_>>();
+
+                    format!("[{}]", values.join(","))
+                })
+                .collect::<Vec<_>>();
+
+            format!(
+                "  {{\"history\":{},\"degree\":{degree},\"underdetermined\":{},\
+                 \"previous\":\"{}\",\"next\":\"{}\",\"rows\":[{}]}}",
+                index + 1,
+                record.is_underdetermined(),
+                record.previous,
+                record.next,
+                rows.join(",")
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if objects.is_empty() {
+        "[]\n".into()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
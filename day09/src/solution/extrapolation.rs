@@ -0,0 +1,60 @@
+use super::{difference_rows, number::Number};
+
+/// Extrapolates the history by the offset, `None` if an intermediate value
+/// doesn't fit into `T`.
+///
+/// Positive offsets are counted from the last value and negative ones from the
+/// first value, so `1` is the next value like in part one and `-1` the
+/// previous value like in part two. An offset of zero is the last value.
+///
+/// Instead of extending the pyramid step by step the value is read from its
+/// trailing diagonal with Newton's backward formula
+///
+/// ```text
+/// h(n - 1 + t) = sum(C(t + j - 1, j) * d_j for every row j)
+/// ```
+///
+/// where `d_j` is the last value of row `j`, which takes a single pass over the
+/// rows even for huge offsets.
+pub fn extrapolate<T: Number>(history: &[T], offset: i64) -> Option<T> {
+    if offset == 0 {
+        return Some(history.last().cloned().unwrap_or_else(T::zero));
+    }
+
+    // Going backwards is going forwards through the reversed history.
+    let history = if offset > 0 {
+        history.to_vec()
+    } else {
+        history.iter().rev().cloned().collect()
+    };
+
+    let steps = offset.unsigned_abs();
+    let mut coefficient = T::from_u32(1);
+    let mut value = T::zero();
+
+    for (row, differences) in (0u32..).zip(difference_rows(history)?) {
+        if row > 0 {
+            // C(t + j - 1, j) = C(t + j - 2, j - 1) * (t + j - 1) / j where the
+            // division is exact. Cancelling their common divisor first keeps
+            // the intermediate value below the result.
+            let numerator = steps.checked_add(row as u64 - 1)?;
+            let divisor = greatest_common_divisor(numerator, row.into());
+
+            coefficient = coefficient
+                .try_div_small(row / divisor as u32)?
+                .try_mul(&T::try_from_u64(numerator / divisor)?)?;
+        }
+
+        value = value.try_add(&coefficient.try_mul(differences.last().unwrap())?)?;
+    }
+
+    Some(value)
+}
+
+fn greatest_common_divisor(mut left: u64, mut right: u64) -> u64 {
+    while right != 0 {
+        (left, right) = (right, left % right);
+    }
+
+    left
+}
@@ -0,0 +1,42 @@
+use std::{fmt::Display, str::FromStr};
+
+/// Differences at most this fraction of the largest absolute value of their
+/// history count as zero, they are usually rounding errors of values which
+/// should cancel out. Being relative it works for histories of any magnitude.
+pub const TOLERANCE: f64 = 1e-12;
+
+/// Finite floating point value for real valued measurements.
+///
+/// Unlike `f64` it doesn't parse `NaN` or infinities, so every history starts
+/// out finite and an overflow to infinity can be reported.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Float(pub f64);
+
+impl FromStr for Float {
+    type Err = String;
+
+    fn from_str(raw_number: &str) -> Result<Self, Self::Err> {
+        match raw_number.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(Float(value)),
+            _ => Err(format!("`{raw_number}` is not a finite number.")),
+        }
+    }
+}
+
+impl Display for Float {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Avoids printing `-0` for values which only rounding made negative.
+        if self.0 == 0.0 {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+impl Float {
+    /// `None` if the value isn't finite.
+    pub fn finite(value: f64) -> Option<Self> {
+        value.is_finite().then_some(Float(value))
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+use super::rational::Rational;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GapError {
+    /// The polynomial of minimal degree through the known values passes
+    /// through every one of them, so any missing values would fit.
+    Underdetermined {
+        known: usize,
+    },
+    /// A missing value of a report which should only contain integers isn't
+    /// one, `position` counts from one.
+    Fractional {
+        position: usize,
+        value: Rational,
+    },
+    InvalidValue(String),
+}
+
+impl Display for GapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GapError::Underdetermined { known } => write!(
+                f,
+                "The {known} known values don't determine the missing ones, the polynomial \
+                 through them uses every one of them."
+            ),
+            GapError::Fractional { position, value } => write!(
+                f,
+                "The missing value at position {position} is {value} and not an integer, \
+                 only `--number rational` keeps it."
+            ),
+            GapError::InvalidValue(raw_value) => {
+                write!(f, "`{raw_value}` is neither a number nor `?`.")
+            }
+        }
+    }
+}
+
+/// Parses a history in which `?` marks missing values, the known values may
+/// be integers, decimals or fractions.
+pub fn parse_history(line: &str) -> Result<Vec<Option<Rational>>, GapError> {
+    line.split_whitespace()
+        .map(|raw_value| match raw_value {
+            "?" => Ok(None),
+            raw_value => raw_value
+                .parse()
+                .map(Some)
+                .map_err(|_| GapError::InvalidValue(raw_value.to_string())),
+        })
+        .collect()
+}
+
+/// Replaces the missing values with the values of the polynomial of minimal
+/// degree through the known ones.
+///
+/// The polynomial is built in Newton form from the divided differences of the
+/// known values, its degree is the index of the last divided difference which
+/// isn't zero. Like for complete histories it only counts as determined if at
+/// least one known value is left over to confirm it. Histories without gaps
+/// are always fine.
+pub fn fill_gaps(history: &[Option<Rational>]) -> Result<Vec<Rational>, GapError> {
+    if history.iter().all(Option::is_some) {
+        return Ok(history.iter().flatten().cloned().collect());
+    }
+
+    let (positions, mut differences): (Vec<_>, Vec<_>) = history
+        .iter()
+        .enumerate()
+        .filter_map(|(position, value)| {
+            let value = value.clone()?;
+            Some((Rational::from(position as i64), value))
+        })
+        .unzip();
+
+    let known = positions.len();
+
+    for level in 1..known {
+        for index in (level..known).rev() {
+            differences[index] = differences[index]
+                .sub(&differences[index - 1])
+                .div(&positions[index].sub(&positions[index - level]));
+        }
+    }
+
+    let zero = Rational::from(0i64);
+    let degree = differences
+        .iter()
+        .rposition(|difference| *difference != zero);
+
+    if known == 0 || degree == Some(known - 1) {
+        return Err(GapError::Underdetermined { known });
+    }
+
+    let newton_terms = degree.map_or(0, |degree| degree + 1);
+
+    Ok(history
+        .iter()
+        .enumerate()
+        .map(|(position, value)| match value {
+            Some(value) => value.clone(),
+            None => {
+                let x = Rational::from(position as i64);
+
+                (0..newton_terms).rev().fold(zero.clone(), |result, term| {
+                    result.mul(&x.sub(&positions[term])).add(&differences[term])
+                })
+            }
+        })
+        .collect())
+}
+
+/// Fills the gaps of every history in the report and returns the report
+/// without gaps, known values are kept as they are written.
+///
+/// Missing values which aren't integers are written as fractions if
+/// `fractions` is set and are an error otherwise, since only rationals can
+/// hold them exactly.
+pub fn fill_report(input: &str, fractions: bool) -> Result<String, String> {
+    let mut report = String::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let history_error = |error: GapError| format!("History {}: {error}", index + 1);
+        let history = parse_history(line).map_err(history_error)?;
+        let filled = fill_gaps(&history).map_err(history_error)?;
+
+        let values = line
+            .split_whitespace()
+            .zip(history.iter().zip(filled))
+            .enumerate()
+            .map(|(position, (raw_value, (known, value)))| match known {
+                Some(_) => Ok(raw_value.to_string()),
+                None if fractions || value.is_integer() => Ok(value.to_string()),
+                None => Err(history_error(GapError::Fractional {
+                    position: position + 1,
+                    value,
+                })),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        report.push_str(&values.join(" "));
+        report.push('\n');
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,90 @@
+use std::fmt::Display;
+
+use super::{
+    big_int::BigInt,
+    classification::{classify, Classification},
+    extrapolation::extrapolate,
+    rational::Rational,
+    recurrence::Recurrence,
+};
+
+/// What a prediction is based on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Model {
+    /// The difference pyramid reaches a row of zeros, `None` for histories
+    /// which are all zeros.
+    Polynomial { degree: Option<usize> },
+    /// No polynomial fits, but a linear recurrence short enough to be
+    /// confirmed by the values of the history.
+    Recurrence(Recurrence),
+    /// Neither fits, the prediction comes from the polynomial through every
+    /// value like in the puzzle.
+    Underdetermined,
+}
+
+impl Display for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Model::Polynomial {
+                degree: Some(degree),
+            } => {
+                write!(f, "polynomial of degree {degree}")
+            }
+            Model::Polynomial { degree: None } => write!(f, "zero polynomial"),
+            Model::Recurrence(recurrence) => write!(f, "recurrence {recurrence}"),
+            Model::Underdetermined => write!(f, "underdetermined polynomial"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prediction {
+    pub model: Model,
+    pub next: Rational,
+    /// `None` if the recurrence can't be run backwards.
+    pub previous: Option<Rational>,
+}
+
+/// Predicts the next and previous value of the history with the first model
+/// which fits it.
+///
+/// Polynomials are tried first. If the pyramid doesn't reach zeros the
+/// shortest linear recurrence is used instead as long as it is confirmed,
+/// i. e. its order `L` is below half the length, as `2L` values always fit some
+/// recurrence of order `L`.
+pub fn predict(history: &[BigInt]) -> Prediction {
+    let classification = classify(history).expect("Big integers can't overflow.");
+
+    if let Classification::Polynomial { degree, .. } = classification {
+        return polynomial_prediction(history, Model::Polynomial { degree });
+    }
+
+    let sequence = history
+        .iter()
+        .cloned()
+        .map(Rational::from)
+        .collect::<Vec<_>>();
+    let recurrence = Recurrence::find(&sequence);
+
+    if 2 * recurrence.order() < sequence.len() {
+        return Prediction {
+            next: recurrence.next_value(&sequence),
+            previous: recurrence.previous_value(&sequence),
+            model: Model::Recurrence(recurrence),
+        };
+    }
+
+    polynomial_prediction(history, Model::Underdetermined)
+}
+
+fn polynomial_prediction(history: &[BigInt], model: Model) -> Prediction {
+    let value = |offset| {
+        Rational::from(extrapolate(history, offset).expect("Big integers can't overflow."))
+    };
+
+    Prediction {
+        next: value(1),
+        previous: Some(value(-1)),
+        model,
+    }
+}
@@ -0,0 +1,138 @@
+use super::big_int::BigInt;
+
+/// Primes just below `2^61`, `2^62` and `2^63` which together recover sums up
+/// to about `2^185` in absolute value.
+pub const DEFAULT_PRIMES: [u64; 3] = [
+    2_305_843_009_213_693_951,
+    4_611_686_018_427_387_847,
+    9_223_372_036_854_775_783,
+];
+
+/// Deterministic Miller-Rabin test, these bases are enough for every `u64`.
+pub fn is_prime(number: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if number < 2 {
+        return false;
+    }
+
+    if let Some(&base) = BASES.iter().find(|&&base| number.is_multiple_of(base)) {
+        return number == base;
+    }
+
+    let trailing_zeros = (number - 1).trailing_zeros();
+    let odd_part = (number - 1) >> trailing_zeros;
+
+    BASES.iter().all(|&base| {
+        let mut value = power(base, odd_part, number);
+
+        if value == 1 || value == number - 1 {
+            return true;
+        }
+
+        (1..trailing_zeros).any(|_| {
+            value = multiply(value, value, number);
+            value == number - 1
+        })
+    })
+}
+
+/// The next value of the history modulo the prime, `None` if the history has
+/// at least `prime` values.
+///
+/// Works like the closed form as the pyramid can't be used: a difference
+/// which is only zero modulo the prime would end it too early.
+pub fn next_value_modulo(history: &[BigInt], prime: u64) -> Option<u64> {
+    let length = history.len() as u64;
+
+    if length >= prime {
+        return None;
+    }
+
+    let mut coefficient = 1;
+    let mut prediction = 0;
+
+    for (index, value) in (0..length).zip(history) {
+        let term = multiply(coefficient, value.rem_euclid_u64(prime), prime);
+
+        prediction = if (length - 1 - index).is_multiple_of(2) {
+            add(prediction, term, prime)
+        } else {
+            add(prediction, prime - term, prime)
+        };
+
+        // C(n, i + 1) = C(n, i) * (n - i) / (i + 1) where `i + 1` is invertible
+        // as it is below the prime.
+        coefficient = multiply(
+            multiply(coefficient, length - index, prime),
+            power(index + 1, prime - 2, prime),
+            prime,
+        );
+    }
+
+    Some(prediction)
+}
+
+/// The sum of the next values (or the previous ones if `backwards`) of all
+/// histories modulo the prime, `None` if a history has at least `prime`
+/// values.
+pub fn sum_modulo(histories: &[Vec<BigInt>], prime: u64, backwards: bool) -> Option<u64> {
+    histories.iter().try_fold(0, |sum, history| {
+        let prediction = if backwards {
+            let reversed = history.iter().rev().cloned().collect::<Vec<_>>();
+            next_value_modulo(&reversed, prime)?
+        } else {
+            next_value_modulo(history, prime)?
+        };
+
+        Some(add(sum, prediction, prime))
+    })
+}
+
+/// The number with the residues modulo the distinct primes which is closest to
+/// zero, i. e. the exact value if its absolute value is below half the product
+/// of the primes.
+pub fn reconstruct(residues: &[(u64, u64)]) -> BigInt {
+    let mut value = BigInt::from(0i64);
+    let mut modulus = BigInt::from(1i64);
+
+    for &(residue, prime) in residues {
+        // Adds the multiple of the modulus which fixes the residue.
+        let difference = add(residue, prime - value.rem_euclid_u64(prime), prime);
+        let inverse = power(modulus.rem_euclid_u64(prime), prime - 2, prime);
+        let factor = multiply(difference, inverse, prime);
+
+        value = value.add(&modulus.mul(&BigInt::from(factor)));
+        modulus = modulus.mul(&BigInt::from(prime));
+    }
+
+    if value > modulus.div_small(2) {
+        value.sub(&modulus)
+    } else {
+        value
+    }
+}
+
+fn add(left: u64, right: u64, modulus: u64) -> u64 {
+    ((left as u128 + right as u128) % modulus as u128) as u64
+}
+
+fn multiply(left: u64, right: u64, modulus: u64) -> u64 {
+    ((left as u128 * right as u128) % modulus as u128) as u64
+}
+
+fn power(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    base %= modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(result, base, modulus);
+        }
+
+        base = multiply(base, base, modulus);
+        exponent >>= 1;
+    }
+
+    result
+}
@@ -0,0 +1,187 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use super::{
+    big_int::BigInt,
+    float::{Float, TOLERANCE},
+    rational::Rational,
+};
+
+/// Numbers a history can consist of, every operation reports an overflow
+/// with `None` instead of wrapping around.
+pub trait Number: Clone + PartialEq + Debug + Display + FromStr<Err: Debug> {
+    fn zero() -> Self;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    /// The magnitude of the values differences of them are compared against
+    /// by [`Number::is_negligible`], only approximate types need one.
+    fn scale(_values: &[Self]) -> Self {
+        Self::zero()
+    }
+
+    /// Whether the value counts as zero next to values of the scale, exact
+    /// types only if it is zero.
+    fn is_negligible(&self, _scale: &Self) -> bool {
+        self.is_zero()
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self>;
+
+    fn try_sub(&self, other: &Self) -> Option<Self>;
+
+    fn try_mul(&self, other: &Self) -> Option<Self>;
+
+    /// Divides by a small divisor, only used where the division is exact.
+    fn try_div_small(&self, divisor: u32) -> Option<Self>;
+
+    fn from_u32(value: u32) -> Self;
+
+    fn try_from_u64(value: u64) -> Option<Self>;
+}
+
+macro_rules! impl_number_for_primitive {
+    ($($primitive:ty),*) => {
+        $(
+            impl Number for $primitive {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn try_add(&self, other: &Self) -> Option<Self> {
+                    self.checked_add(*other)
+                }
+
+                fn try_sub(&self, other: &Self) -> Option<Self> {
+                    self.checked_sub(*other)
+                }
+
+                fn try_mul(&self, other: &Self) -> Option<Self> {
+                    self.checked_mul(*other)
+                }
+
+                fn try_div_small(&self, divisor: u32) -> Option<Self> {
+                    self.checked_div(divisor.into())
+                }
+
+                fn from_u32(value: u32) -> Self {
+                    value.into()
+                }
+
+                fn try_from_u64(value: u64) -> Option<Self> {
+                    value.try_into().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_number_for_primitive!(i64, i128);
+
+impl Number for BigInt {
+    fn zero() -> Self {
+        BigInt::from(0i64)
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self> {
+        Some(self.add(other))
+    }
+
+    fn try_sub(&self, other: &Self) -> Option<Self> {
+        Some(self.sub(other))
+    }
+
+    fn try_mul(&self, other: &Self) -> Option<Self> {
+        Some(self.mul(other))
+    }
+
+    fn try_div_small(&self, divisor: u32) -> Option<Self> {
+        (divisor != 0).then(|| self.div_small(divisor))
+    }
+
+    fn from_u32(value: u32) -> Self {
+        BigInt::from(value as u64)
+    }
+
+    fn try_from_u64(value: u64) -> Option<Self> {
+        Some(BigInt::from(value))
+    }
+}
+
+impl Number for Rational {
+    fn zero() -> Self {
+        Rational::from(0i64)
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self> {
+        Some(self.add(other))
+    }
+
+    fn try_sub(&self, other: &Self) -> Option<Self> {
+        Some(self.sub(other))
+    }
+
+    fn try_mul(&self, other: &Self) -> Option<Self> {
+        Some(self.mul(other))
+    }
+
+    fn try_div_small(&self, divisor: u32) -> Option<Self> {
+        (divisor != 0).then(|| self.div(&Rational::from(divisor as i64)))
+    }
+
+    fn from_u32(value: u32) -> Self {
+        Rational::from(value as i64)
+    }
+
+    fn try_from_u64(value: u64) -> Option<Self> {
+        Some(Rational::from(BigInt::from(value)))
+    }
+}
+
+/// Operations which leave the finite values report an overflow.
+impl Number for Float {
+    fn zero() -> Self {
+        Float(0.0)
+    }
+
+    /// The largest absolute value.
+    fn scale(values: &[Self]) -> Self {
+        Float(
+            values
+                .iter()
+                .fold(0.0, |scale, value| scale.max(value.0.abs())),
+        )
+    }
+
+    fn is_negligible(&self, scale: &Self) -> bool {
+        self.0.abs() <= TOLERANCE * scale.0
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self> {
+        Float::finite(self.0 + other.0)
+    }
+
+    fn try_sub(&self, other: &Self) -> Option<Self> {
+        Float::finite(self.0 - other.0)
+    }
+
+    fn try_mul(&self, other: &Self) -> Option<Self> {
+        Float::finite(self.0 * other.0)
+    }
+
+    fn try_div_small(&self, divisor: u32) -> Option<Self> {
+        Float::finite(self.0 / divisor as f64)
+    }
+
+    fn from_u32(value: u32) -> Self {
+        Float(value as f64)
+    }
+
+    fn try_from_u64(value: u64) -> Option<Self> {
+        Some(Float(value as f64))
+    }
+}
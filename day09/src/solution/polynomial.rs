@@ -0,0 +1,108 @@
+use std::fmt::Display;
+
+use super::{big_int::BigInt, difference_rows, rational::Rational};
+
+/// Polynomial with exact coefficients in the monomial basis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    /// The coefficient of `x^k` at index `k` without trailing zeros.
+    coefficients: Vec<Rational>,
+}
+
+impl Display for Polynomial {
+    /// Writes the terms from the highest power down e. g.
+    /// `(1/2)x^2 + (3/2)x + 1`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let zero = Rational::from(0i64);
+        let one = Rational::from(1i64);
+        let mut first = true;
+
+        for (power, coefficient) in self.coefficients.iter().enumerate().rev() {
+            if *coefficient == zero {
+                continue;
+            }
+
+            let magnitude = if *coefficient < zero {
+                write!(f, "{}", if first { "-" } else { " - " })?;
+                coefficient.negate()
+            } else {
+                if !first {
+                    write!(f, " + ")?;
+                }
+                coefficient.clone()
+            };
+
+            first = false;
+
+            match (power, magnitude == one, magnitude.is_integer()) {
+                (0, _, _) => write!(f, "{magnitude}")?,
+                (_, true, _) => {}
+                (_, _, true) => write!(f, "{magnitude}")?,
+                (_, _, false) => write!(f, "({magnitude})")?,
+            }
+
+            match power {
+                0 => {}
+                1 => write!(f, "x")?,
+                power => write!(f, "x^{power}")?,
+            }
+        }
+
+        if first {
+            write!(f, "0")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Polynomial {
+    /// The polynomial of minimal degree through every value of the history,
+    /// where the first value is at `x = 0`, the second at `x = 1` and so on.
+    ///
+    /// The pyramid gives its Newton form `sum(d_j * C(x, j))` where `d_j` is the
+    /// first value of row `j`, which is expanded by multiplying out the falling
+    /// factorials `x (x - 1) ... (x - j + 1)` and dividing them by `j!`.
+    pub fn fit(history: &[BigInt]) -> Self {
+        let rows = difference_rows(history.to_vec()).expect("Big integers can't overflow.");
+
+        let mut coefficients = vec![Rational::from(0i64); rows.len()];
+        let mut falling_factorial = vec![BigInt::from(1i64)];
+        let mut factorial = BigInt::from(1i64);
+
+        for (row_index, row) in rows.iter().enumerate() {
+            if row_index > 0 {
+                let root = BigInt::from(row_index as i64 - 1);
+                factorial = factorial.mul(&BigInt::from(row_index as i64));
+
+                // Multiplies by `x - root`.
+                let mut product = vec![BigInt::from(0i64); falling_factorial.len() + 1];
+
+                for (power, coefficient) in falling_factorial.iter().enumerate() {
+                    product[power + 1] = product[power + 1].add(coefficient);
+                    product[power] = product[power].sub(&coefficient.mul(&root));
+                }
+
+                falling_factorial = product;
+            }
+
+            let scale = Rational::new(row[0].clone(), factorial.clone());
+
+            for (power, coefficient) in falling_factorial.iter().enumerate() {
+                coefficients[power] =
+                    coefficients[power].add(&scale.mul(&Rational::from(coefficient.clone())));
+            }
+        }
+
+        while coefficients.last() == Some(&Rational::from(0i64)) {
+            coefficients.pop();
+        }
+
+        Self { coefficients }
+    }
+
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+}
@@ -0,0 +1,75 @@
+use super::number::Number;
+
+/// Predicts the next value of a history which arrives one value at a time.
+///
+/// Only the trailing diagonal of the difference pyramid is kept, i. e. the
+/// last value of every row, without the rows of zeros at the bottom. Its sum is
+/// the prediction of `predict_next_value`, so a history of degree `d` needs
+/// `d + 1` values of state no matter how long it gets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Predictor<T> {
+    diagonal: Vec<T>,
+    count: u64,
+    /// The scale of all values so far, see [`Number::scale`].
+    scale: T,
+}
+
+impl<T: Number> Default for Predictor<T> {
+    fn default() -> Self {
+        Self {
+            diagonal: vec![],
+            count: 0,
+            scale: T::zero(),
+        }
+    }
+}
+
+impl<T: Number> Predictor<T> {
+    /// Adds the next value of the history and returns the new prediction,
+    /// `None` if a difference or the prediction doesn't fit into `T` in which
+    /// case the predictor is left unchanged.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let scale = T::scale(&[self.scale.clone(), value.clone()]);
+        let mut diagonal = vec![value];
+
+        // Row `row` gets a new last value for every row which already existed
+        // and one new row, rows below the stored diagonal are all zeros.
+        for row in 1..=self.count {
+            let above = diagonal.last().unwrap();
+            let previous = self.diagonal.get(row as usize - 1);
+
+            if previous.is_none() && above.is_negligible(&scale) {
+                break;
+            }
+
+            let difference = match previous {
+                Some(previous) => above.try_sub(previous)?,
+                None => above.clone(),
+            };
+
+            diagonal.push(difference);
+        }
+
+        while diagonal
+            .last()
+            .is_some_and(|value| value.is_negligible(&scale))
+        {
+            diagonal.pop();
+        }
+
+        let prediction = diagonal
+            .iter()
+            .try_fold(T::zero(), |sum, value| sum.try_add(value))?;
+
+        self.diagonal = diagonal;
+        self.count += 1;
+        self.scale = scale;
+        Some(prediction)
+    }
+
+    /// The degree of the history so far, which is also the amount of values of
+    /// state minus one. `None` while the history is all zeros.
+    pub fn degree(&self) -> Option<usize> {
+        self.diagonal.len().checked_sub(1)
+    }
+}
@@ -0,0 +1,159 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use super::big_int::BigInt;
+
+/// Exact fraction in lowest terms with a positive denominator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rational {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl From<BigInt> for Rational {
+    fn from(integer: BigInt) -> Self {
+        Self {
+            numerator: integer,
+            denominator: BigInt::from(1i64),
+        }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(integer: i64) -> Self {
+        Self::from(BigInt::from(integer))
+    }
+}
+
+impl FromStr for Rational {
+    type Err = String;
+
+    /// Parses integers, decimals like `-12.5` and fractions like `3/4`.
+    fn from_str(raw_number: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("`{raw_number}` is not a rational number.");
+
+        if let Some((numerator, denominator)) = raw_number.split_once('/') {
+            let numerator = numerator.parse::<BigInt>().map_err(|_| invalid())?;
+            let denominator = denominator.parse::<BigInt>().map_err(|_| invalid())?;
+
+            if denominator == BigInt::from(0i64) {
+                return Err(format!("`{raw_number}` has a zero denominator."));
+            }
+
+            return Ok(Self::new(numerator, denominator));
+        }
+
+        let (negative, unsigned) = match raw_number.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, raw_number.strip_prefix('+').unwrap_or(raw_number)),
+        };
+
+        let (integer_digits, fraction_digits) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+        // `BigInt` parses the sign itself, so only digits may be left.
+        if integer_digits.len() + fraction_digits.len() == 0
+            || !(integer_digits.bytes().chain(fraction_digits.bytes()))
+                .all(|digit| digit.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let digits = format!("{integer_digits}{fraction_digits}");
+        let magnitude = digits.parse::<BigInt>().map_err(|_| invalid())?;
+        let denominator = format!("1{}", "0".repeat(fraction_digits.len()))
+            .parse::<BigInt>()
+            .map_err(|_| invalid())?;
+
+        let numerator = if negative {
+            magnitude.negate()
+        } else {
+            magnitude
+        };
+        Ok(Self::new(numerator, denominator))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numerator
+            .mul(&other.denominator)
+            .cmp(&other.numerator.mul(&self.denominator))
+    }
+}
+
+impl Rational {
+    /// # Panics
+    ///
+    /// If the denominator is zero.
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Self {
+        assert!(denominator != BigInt::from(0i64), "Zero denominator.");
+
+        let divisor = numerator.greatest_common_divisor(&denominator);
+        let divisor = if denominator.is_negative() {
+            divisor.negate()
+        } else {
+            divisor
+        };
+
+        Self {
+            numerator: numerator.div_rem(&divisor).0,
+            denominator: denominator.div_rem(&divisor).0,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == BigInt::from(1i64)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self::new(
+            self.numerator
+                .mul(&other.denominator)
+                .add(&other.numerator.mul(&self.denominator)),
+            self.denominator.mul(&other.denominator),
+        )
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.negate())
+    }
+
+    pub fn negate(&self) -> Self {
+        Self {
+            numerator: self.numerator.negate(),
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self::new(
+            self.numerator.mul(&other.numerator),
+            self.denominator.mul(&other.denominator),
+        )
+    }
+
+    /// # Panics
+    ///
+    /// If `other` is zero.
+    pub fn div(&self, other: &Self) -> Self {
+        Self::new(
+            self.numerator.mul(&other.denominator),
+            self.denominator.mul(&other.numerator),
+        )
+    }
+}
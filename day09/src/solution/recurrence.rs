@@ -0,0 +1,156 @@
+use std::fmt::Display;
+
+use super::rational::Rational;
+
+/// Linear recurrence `s(n) = c_1 s(n - 1) + ... + c_L s(n - L)` with exact
+/// coefficients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    /// `c_1` to `c_L`, the last ones may be zero if the recurrence only holds
+    /// from `s(L)` on.
+    coefficients: Vec<Rational>,
+}
+
+impl Display for Recurrence {
+    /// Writes the recurrence e. g. `s(n) = s(n-1) + s(n-2)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let zero = Rational::from(0i64);
+        let one = Rational::from(1i64);
+        let mut first = true;
+
+        write!(f, "s(n) =")?;
+
+        for (offset, coefficient) in (1..).zip(&self.coefficients) {
+            if *coefficient == zero {
+                continue;
+            }
+
+            let magnitude = match (*coefficient < zero, first) {
+                (true, true) => {
+                    write!(f, " -")?;
+                    coefficient.negate()
+                }
+                (true, false) => {
+                    write!(f, " - ")?;
+                    coefficient.negate()
+                }
+                (false, true) => {
+                    write!(f, " ")?;
+                    coefficient.clone()
+                }
+                (false, false) => {
+                    write!(f, " + ")?;
+                    coefficient.clone()
+                }
+            };
+
+            first = false;
+
+            match (magnitude == one, magnitude.is_integer()) {
+                (true, _) => {}
+                (_, true) => write!(f, "{magnitude} ")?,
+                (_, false) => write!(f, "({magnitude}) ")?,
+            }
+
+            write!(f, "s(n-{offset})")?;
+        }
+
+        if first {
+            write!(f, " 0")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Recurrence {
+    /// The shortest recurrence the whole sequence satisfies, found with the
+    /// Berlekamp-Massey algorithm over the rationals.
+    pub fn find(sequence: &[Rational]) -> Self {
+        let zero = Rational::from(0i64);
+
+        // Connection polynomials `1 + c'_1 x + ...` where `c'_i = -c_i`, the
+        // current one and the one before the last length change.
+        let mut connection = vec![Rational::from(1i64)];
+        let mut previous_connection = vec![Rational::from(1i64)];
+        let mut length = 0;
+        let mut previous_discrepancy = Rational::from(1i64);
+        let mut shift = 1;
+
+        for index in 0..sequence.len() {
+            let discrepancy = (1..=length.min(connection.len() - 1))
+                .fold(sequence[index].clone(), |sum, offset| {
+                    sum.add(&connection[offset].mul(&sequence[index - offset]))
+                });
+
+            if discrepancy == zero {
+                shift += 1;
+                continue;
+            }
+
+            let factor = discrepancy.div(&previous_discrepancy);
+            let mut corrected = connection.clone();
+
+            if corrected.len() < previous_connection.len() + shift {
+                corrected.resize(previous_connection.len() + shift, zero.clone());
+            }
+
+            for (power, coefficient) in previous_connection.iter().enumerate() {
+                corrected[power + shift] = corrected[power + shift].sub(&factor.mul(coefficient));
+            }
+
+            if 2 * length <= index {
+                previous_connection = std::mem::replace(&mut connection, corrected);
+                length = index + 1 - length;
+                previous_discrepancy = discrepancy;
+                shift = 1;
+            } else {
+                connection = corrected;
+                shift += 1;
+            }
+        }
+
+        connection.resize(length + 1, zero);
+
+        Self {
+            coefficients: connection[1..].iter().map(Rational::negate).collect(),
+        }
+    }
+
+    /// The amount of previous values each value depends on.
+    pub fn order(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// The value after the sequence, which has to have at least `order`
+    /// values.
+    pub fn next_value(&self, sequence: &[Rational]) -> Rational {
+        (1..)
+            .zip(&self.coefficients)
+            .fold(Rational::from(0i64), |sum, (offset, coefficient)| {
+                sum.add(&coefficient.mul(&sequence[sequence.len() - offset]))
+            })
+    }
+
+    /// The value before the sequence, which has to have at least `order`
+    /// values, `None` if the recurrence doesn't depend on the value `order`
+    /// steps back and therefore can't be run backwards. The empty recurrence
+    /// of an all zero sequence gives zero.
+    pub fn previous_value(&self, sequence: &[Rational]) -> Option<Rational> {
+        let Some(last) = self.coefficients.last() else {
+            return Some(Rational::from(0i64));
+        };
+
+        if *last == Rational::from(0i64) {
+            return None;
+        }
+
+        // Solves `s(L - 1) = c_1 s(L - 2) + ... + c_L s(-1)` for `s(-1)`.
+        let order = self.order();
+        let known = (1..order).fold(sequence[order - 1].clone(), |rest, offset| {
+            rest.sub(&self.coefficients[offset - 1].mul(&sequence[order - 1 - offset]))
+        });
+
+        Some(known.div(last))
+    }
+}
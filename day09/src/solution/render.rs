@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use super::{difference_rows, number::Number};
+
+const HIGHLIGHT: &str = "\x1b[1;32m";
+const RESET: &str = "\x1b[0m";
+
+/// Renders the pyramid of the history like the puzzle text, every row is
+/// extended by its previous and next value and ends with a row of zeros.
+///
+/// ```text
+/// 5  10  13  16  21  30  45  68
+///   5   3   3   5   9  15  23
+///    -2   0   2   4   6   8
+///       2   2   2   2   2
+///         0   0   0   0
+/// ```
+///
+/// Every value is right aligned in a column wide enough for the widest value
+/// plus two spaces (rounded up to an even width so the rows below can start
+/// half a column further right). With `highlight` the extrapolated values are
+/// colored with ANSI escape codes. `None` if a value doesn't fit into `T`.
+pub fn render_pyramid<T: Number>(history: &[T], highlight: bool) -> Option<String> {
+    let mut rows = difference_rows(history.to_vec())?;
+    rows.push(vec![T::zero(); history.len() - rows.len()]);
+
+    // Extends the rows from the bottom up like the puzzle text.
+    let mut previous = T::zero();
+    let mut next = T::zero();
+    let mut extended_rows = vec![];
+
+    for row in rows.iter().rev() {
+        if let (Some(first), Some(last)) = (row.first(), row.last()) {
+            previous = first.try_sub(&previous)?;
+            next = last.try_add(&next)?;
+        }
+
+        let mut cells = vec![(previous.to_string(), true)];
+        cells.extend(row.iter().map(|value| (value.to_string(), false)));
+        cells.push((next.to_string(), true));
+        extended_rows.push(cells);
+    }
+
+    extended_rows.reverse();
+
+    let widest = extended_rows
+        .iter()
+        .flatten()
+        .map(|(value, _)| value.len())
+        .max()
+        .unwrap_or(0);
+    let width = (widest + 3) / 2 * 2;
+
+    // The puzzle text doesn't indent the first row, so the indentation all
+    // rows share is dropped.
+    let shared_indentation = extended_rows
+        .iter()
+        .enumerate()
+        .map(|(row_index, cells)| row_index * width / 2 + width - cells[0].0.len())
+        .min()
+        .unwrap_or(0);
+
+    let mut pyramid = String::new();
+
+    for (row_index, cells) in extended_rows.iter().enumerate() {
+        let mut line = " ".repeat(row_index * width / 2);
+
+        for (value, extrapolated) in cells {
+            line.push_str(&" ".repeat(width - value.len()));
+
+            if *extrapolated && highlight {
+                write!(line, "{HIGHLIGHT}{value}{RESET}").unwrap();
+            } else {
+                line.push_str(value);
+            }
+        }
+
+        writeln!(pyramid, "{}", &line[shared_indentation..]).unwrap();
+    }
+
+    Some(pyramid)
+}